@@ -0,0 +1,4 @@
+/*!
+ Helpers specific to the Belgian identification numbers (NRN and BIS).
+*/
+pub use crate::validator::belgium::{number_kind, NumberKind};
@@ -0,0 +1,4 @@
+/*!
+ Helpers specific to the Brazilian CPF and to the CNPJ company number.
+*/
+pub use crate::validator::brazil::{explain_cnpj, fiscal_region, format_cnpj, validate_cnpj};
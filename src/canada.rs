@@ -0,0 +1,4 @@
+/*!
+ Helpers specific to the Canadian Social Insurance Number.
+*/
+pub use crate::validator::canada::{is_temporary_resident, registration_province};
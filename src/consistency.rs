@@ -0,0 +1,258 @@
+use crate::validator::names;
+use crate::validator::CountryValidator;
+use crate::{country, validator, Citizen, PlaceOfBirth};
+
+/**
+ Name of a person, as needed by the countries that derive part of the identifier from it.
+
+ `second_last_name` is the maternal surname used in Mexico; elsewhere it is appended to
+ `last_name`.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Name {
+    pub first_name: String,
+    pub last_name: String,
+    pub second_last_name: Option<String>,
+}
+
+/**
+ Outcome of comparing a claimed field with what the identifier encodes.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldCheck {
+    Match,
+    Mismatch,
+    /// The identifier does not encode this field.
+    NotEncoded,
+    /// The field is encoded, but no value was claimed for it.
+    NotClaimed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    pub valid_id: bool,
+    pub gender: FieldCheck,
+    pub year_of_birth: FieldCheck,
+    pub month_of_birth: FieldCheck,
+    pub day_of_birth: FieldCheck,
+    pub place_of_birth: FieldCheck,
+    pub name: FieldCheck,
+}
+
+impl ConsistencyReport {
+    /// True when the identifier is valid and none of the claimed fields contradict it.
+    pub fn is_consistent(&self) -> bool {
+        self.valid_id
+            && [
+                self.gender,
+                self.year_of_birth,
+                self.month_of_birth,
+                self.day_of_birth,
+                self.place_of_birth,
+                self.name,
+            ]
+            .iter()
+            .all(|check| *check != FieldCheck::Mismatch)
+    }
+}
+
+pub fn verify_against(id: &str, country: country::Code, claim: &Citizen) -> ConsistencyReport {
+    let country_validator = validator::get_validator(&country);
+    build_report(country_validator.as_ref(), id, claim, None)
+}
+
+pub fn verify_against_with_name(
+    id: &str,
+    country: country::Code,
+    claim: &Citizen,
+    name: &Name,
+) -> ConsistencyReport {
+    let country_validator = validator::get_validator(&country);
+    build_report(country_validator.as_ref(), id, claim, Some(name))
+}
+
+fn build_report(
+    country_validator: &dyn CountryValidator,
+    id: &str,
+    claim: &Citizen,
+    name: Option<&Name>,
+) -> ConsistencyReport {
+    let valid_id = country_validator.validate_id(id);
+    let name_check = if valid_id && country_validator.encodes_name() {
+        compare(
+            Some(true),
+            name.map(|name| country_validator.matches_name(id, name)),
+        )
+    } else {
+        FieldCheck::NotEncoded
+    };
+
+    let citizen = if valid_id {
+        country_validator.extract_citizen(id)
+    } else {
+        None
+    };
+    if citizen.is_none() {
+        return ConsistencyReport {
+            valid_id,
+            gender: FieldCheck::NotEncoded,
+            year_of_birth: FieldCheck::NotEncoded,
+            month_of_birth: FieldCheck::NotEncoded,
+            day_of_birth: FieldCheck::NotEncoded,
+            place_of_birth: FieldCheck::NotEncoded,
+            name: name_check,
+        };
+    }
+
+    let citizen = citizen.unwrap();
+    ConsistencyReport {
+        valid_id,
+        gender: compare(
+            Some(citizen.gender.to_ascii_uppercase()).filter(|gender| *gender != 'U'),
+            Some(claim.gender.to_ascii_uppercase()),
+        ),
+        year_of_birth: compare(Some(citizen.year_of_birth), Some(claim.year_of_birth)),
+        month_of_birth: compare(citizen.month_of_birth, claim.month_of_birth),
+        day_of_birth: compare(citizen.day_of_birth, claim.day_of_birth),
+        place_of_birth: match (&citizen.place_of_birth, &claim.place_of_birth) {
+            (None, _) => FieldCheck::NotEncoded,
+            (Some(_), None) => FieldCheck::NotClaimed,
+            (Some(encoded), Some(claimed)) => {
+                if places_match(encoded, claimed) {
+                    FieldCheck::Match
+                } else {
+                    FieldCheck::Mismatch
+                }
+            }
+        },
+        name: name_check,
+    }
+}
+
+fn compare<T: PartialEq>(encoded: Option<T>, claimed: Option<T>) -> FieldCheck {
+    match (encoded, claimed) {
+        (None, _) => FieldCheck::NotEncoded,
+        (Some(_), None) => FieldCheck::NotClaimed,
+        (Some(encoded), Some(claimed)) => {
+            if encoded == claimed {
+                FieldCheck::Match
+            } else {
+                FieldCheck::Mismatch
+            }
+        }
+    }
+}
+
+// Places match when they share a code or an ISO 3166-2 subdivision, or when their names do in
+// any of the languages the encoded place is known in.
+fn places_match(encoded: &PlaceOfBirth, claimed: &PlaceOfBirth) -> bool {
+    let same_code = !claimed.code.is_empty() && encoded.code == claimed.code;
+    let same_subdivision =
+        encoded.subdivision.is_some() && encoded.subdivision == claimed.subdivision;
+    same_code
+        || same_subdivision
+        || place_names_match(&encoded.name, &claimed.name)
+        || encoded
+            .names
+            .values()
+            .any(|name| place_names_match(name, &claimed.name))
+}
+
+// Names are compared without case and accents, ignoring the qualifiers some countries add
+// after the name, such as the province in "MILANO (MI)".
+fn place_names_match(encoded: &str, claimed: &str) -> bool {
+    let claimed = names::normalize_name(claimed);
+    let encoded_name = encoded.split(" (").next().unwrap();
+    let encoded_name = encoded_name.split(" - ").next().unwrap();
+    !claimed.is_empty()
+        && (names::normalize_name(encoded) == claimed
+            || names::normalize_name(encoded_name) == claimed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::consistency::{place_names_match, places_match, verify_against, FieldCheck};
+    use crate::country::Code;
+    use crate::{Citizen, PlaceOfBirth};
+
+    fn claim(gender: char, year: i32, month: Option<u8>, day: Option<u8>) -> Citizen {
+        Citizen {
+            gender,
+            year_of_birth: year,
+            month_of_birth: month,
+            day_of_birth: day,
+            place_of_birth: None,
+        }
+    }
+
+    #[test]
+    fn matching_claim() {
+        let report = verify_against("090792-1395", Code::DK, &claim('M', 1992, Some(7), Some(9)));
+        assert!(report.valid_id);
+        assert!(report.is_consistent());
+        assert_eq!(report.gender, FieldCheck::Match);
+        assert_eq!(report.day_of_birth, FieldCheck::Match);
+        assert_eq!(report.place_of_birth, FieldCheck::NotEncoded);
+        assert_eq!(report.name, FieldCheck::NotEncoded);
+    }
+
+    #[test]
+    fn mismatching_claim() {
+        let report = verify_against("090792-1395", Code::DK, &claim('f', 1992, Some(7), None));
+        assert!(!report.is_consistent());
+        assert_eq!(report.gender, FieldCheck::Mismatch);
+        assert_eq!(report.month_of_birth, FieldCheck::Match);
+        assert_eq!(report.day_of_birth, FieldCheck::NotClaimed);
+    }
+
+    #[test]
+    fn nothing_is_encoded_for_invalid_or_opaque_ids() {
+        let report = verify_against("090792-1396", Code::DK, &claim('F', 1992, None, None));
+        assert!(!report.valid_id);
+        assert!(!report.is_consistent());
+        assert_eq!(report.gender, FieldCheck::NotEncoded);
+
+        let report = verify_against("86095742719", Code::DE, &claim('F', 1992, None, None));
+        assert!(report.is_consistent());
+        assert_eq!(report.year_of_birth, FieldCheck::NotEncoded);
+    }
+
+    #[test]
+    fn unknown_gender_is_not_encoded() {
+        let report = verify_against(
+            "85.23.14-124.44",
+            Code::BE,
+            &claim('M', 1985, Some(3), Some(14)),
+        );
+        assert!(report.is_consistent());
+        assert_eq!(report.gender, FieldCheck::NotEncoded);
+        assert_eq!(report.month_of_birth, FieldCheck::Match);
+    }
+
+    #[test]
+    fn place_comparison() {
+        assert!(place_names_match("MILANO (MI)", "Milano"));
+        assert!(place_names_match("Corrèze", "correze"));
+        assert!(place_names_match(
+            "Mostar - Bosnia and Herzegovina",
+            "Mostar"
+        ));
+        assert!(!place_names_match("MILANO (MI)", "Roma"));
+        assert!(!place_names_match("MILANO (MI)", ""));
+
+        let milano = PlaceOfBirth::new("F205", "MILANO (MI)")
+            .subdivision("IT-MI")
+            .country("IT");
+        assert!(places_match(&milano, &PlaceOfBirth::named("Milano")));
+        assert!(places_match(&milano, &PlaceOfBirth::new("F205", "Milan")));
+        assert!(places_match(
+            &milano,
+            &PlaceOfBirth::named("Milan").subdivision("IT-MI")
+        ));
+        assert!(!places_match(&milano, &PlaceOfBirth::named("Milan")));
+        assert!(places_match(
+            &milano.translation("en", "MILAN (MI)"),
+            &PlaceOfBirth::named("Milan")
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{country, validator};
+
+const ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Characters that are commonly mistaken for each other when typed or read by OCR.
+const CONFUSION_PAIRS: [(char, char); 10] = [
+    ('0', 'O'),
+    ('0', 'D'),
+    ('0', 'Q'),
+    ('1', 'I'),
+    ('1', 'L'),
+    ('2', 'Z'),
+    ('5', 'S'),
+    ('6', 'G'),
+    ('8', 'B'),
+    ('U', 'V'),
+];
+
+const CONFUSION_COST: u8 = 0;
+const TRANSPOSITION_COST: u8 = 1;
+const SUBSTITUTION_COST: u8 = 2;
+
+/**
+ Suggests valid identifiers that are one typo away from an invalid one.
+
+ Candidates are built by replacing a single character or swapping two adjacent characters.
+ Those that pass the country validation are ranked with replacements of easily confused
+ characters (0/O, 1/I, 5/S, ...) first, then swaps, then any other replacement.
+**/
+pub fn suggest_corrections(id: &str, country: country::Code) -> Vec<String> {
+    let country_validator = validator::get_validator(&country);
+    let standard_id = country_validator.sanitize_id(id);
+    if standard_id.is_empty() || country_validator.validate_id(&standard_id) {
+        return vec![];
+    }
+
+    let chars: Vec<char> = standard_id.chars().collect();
+    let mut candidates: Vec<(u8, String)> = vec![];
+    for idx in 0..chars.len() {
+        for replacement in ALPHABET.chars().filter(|c| *c != chars[idx]) {
+            let mut candidate = chars.clone();
+            candidate[idx] = replacement;
+            let cost = if are_confusable(chars[idx], replacement) {
+                CONFUSION_COST
+            } else {
+                SUBSTITUTION_COST
+            };
+            candidates.push((cost, candidate.into_iter().collect()));
+        }
+
+        if idx + 1 < chars.len() && chars[idx] != chars[idx + 1] {
+            let mut candidate = chars.clone();
+            candidate.swap(idx, idx + 1);
+            candidates.push((TRANSPOSITION_COST, candidate.into_iter().collect()));
+        }
+    }
+
+    candidates.sort_by_key(|(cost, _)| *cost);
+    let mut suggestions: Vec<String> = vec![];
+    for (_, candidate) in candidates {
+        if !suggestions.contains(&candidate) && country_validator.validate_id(&candidate) {
+            suggestions.push(candidate);
+        }
+    }
+    suggestions
+}
+
+fn are_confusable(first: char, second: char) -> bool {
+    CONFUSION_PAIRS
+        .iter()
+        .any(|&(a, b)| (a == first && b == second) || (a == second && b == first))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::corrections::{are_confusable, suggest_corrections};
+    use crate::country::Code;
+
+    #[test]
+    fn confusion_pairs_are_symmetric() {
+        assert!(are_confusable('0', 'O'));
+        assert!(are_confusable('O', '0'));
+        assert!(are_confusable('5', 'S'));
+        assert!(!are_confusable('5', '6'));
+    }
+
+    #[test]
+    fn valid_ids_have_no_suggestions() {
+        assert!(suggest_corrections("167-38-1265", Code::US).is_empty());
+        assert!(suggest_corrections("", Code::US).is_empty());
+    }
+
+    #[test]
+    fn suggests_single_substitutions() {
+        let suggestions = suggest_corrections("144.416.762-64", Code::BR);
+        assert!(suggestions.contains(&"14441676263".to_string()));
+    }
+
+    #[test]
+    fn suggests_adjacent_transpositions() {
+        let suggestions = suggest_corrections("046 454 268", Code::CA);
+        assert!(suggestions.contains(&"046454286".to_string()));
+    }
+
+    #[test]
+    fn ranks_confusable_characters_first() {
+        let suggestions = suggest_corrections("O46454286", Code::CA);
+        assert_eq!(suggestions[0], "046454286");
+
+        let suggestions = suggest_corrections("MRTMTT25D09F2O5Z", Code::IT);
+        assert_eq!(suggestions[0], "MRTMTT25D09F205Z");
+    }
+}
@@ -1,8 +1,8 @@
-use strum_macros::EnumIter;
+use strum_macros::{EnumIter, EnumString};
 /**
 List of ISO country codes: https://en.wikipedia.org/wiki/List_of_ISO_3166_country_codes
 **/
-#[derive(EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter, EnumString)]
 pub enum Code {
     AL,
     BA,
@@ -0,0 +1,6 @@
+/*!
+ Helpers specific to the Danish CPR number.
+*/
+pub use crate::validator::denmark::{
+    extract_cpr, passes_modulus_11, validate_id_without_modulus_11, CprNumber,
+};
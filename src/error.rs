@@ -0,0 +1,24 @@
+use std::error::Error;
+use std::fmt;
+
+/**
+ Reasons why an identifier could not be accepted for a country.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Nothing is left of the identifier once separators are removed.
+    Empty,
+    /// The identifier does not pass the country's validation rules.
+    Invalid,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Empty => write!(f, "the identifier is empty"),
+            ValidationError::Invalid => write!(f, "the identifier is not valid"),
+        }
+    }
+}
+
+impl Error for ValidationError {}
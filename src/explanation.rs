@@ -0,0 +1,194 @@
+use std::fmt;
+
+/**
+ A single check performed while validating an identifier.
+
+ `details` holds the values the check looked at, in the order they were used, such as the
+ expected and actual length or the weights and running sum of a checksum.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub check: &'static str,
+    pub passed: bool,
+    pub details: Vec<(String, String)>,
+    trace: bool,
+}
+
+impl Step {
+    pub(crate) fn new(check: &'static str, passed: bool) -> Step {
+        Step {
+            check,
+            passed,
+            details: vec![],
+            trace: true,
+        }
+    }
+
+    pub(crate) fn detail<T: fmt::Display>(self, label: &str, value: T) -> Step {
+        self.detail_with(label, || value)
+    }
+
+    // Details are only built when the steps are traced, plain validation skips the formatting.
+    pub(crate) fn detail_with<T: fmt::Display, F: FnOnce() -> T>(
+        mut self,
+        label: &str,
+        value: F,
+    ) -> Step {
+        if self.trace {
+            self.details.push((label.to_string(), value().to_string()));
+        }
+        self
+    }
+}
+
+/**
+ Ordered trace of the checks a country validator performed on an identifier.
+
+ Validation stops at the first failing check, so the last step of an invalid identifier is
+ the reason it was rejected.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub input: String,
+    pub sanitized: String,
+    pub steps: Vec<Step>,
+    trace: bool,
+}
+
+impl Explanation {
+    /**
+     Starts an explanation that only records whether each check passed when `trace` is false,
+     which is all `validate_id` needs.
+    **/
+    pub(crate) fn with_trace(input: &str, sanitized: &str, trace: bool) -> Explanation {
+        if !trace {
+            return Explanation {
+                input: String::new(),
+                sanitized: String::new(),
+                steps: vec![],
+                trace,
+            };
+        }
+
+        Explanation {
+            input: input.to_string(),
+            sanitized: sanitized.to_string(),
+            steps: vec![],
+            trace,
+        }
+    }
+
+    pub(crate) fn step(&self, check: &'static str, passed: bool) -> Step {
+        Step {
+            trace: self.trace,
+            ..Step::new(check, passed)
+        }
+    }
+
+    pub(crate) fn check(&mut self, step: Step) -> bool {
+        let passed = step.passed;
+        self.steps.push(step);
+        passed
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|step| step.passed)
+    }
+
+    pub fn failed_step(&self) -> Option<&Step> {
+        self.steps.iter().find(|step| !step.passed)
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "input: {:?}", self.input)?;
+        writeln!(f, "sanitized: {:?}", self.sanitized)?;
+        for step in &self.steps {
+            write!(
+                f,
+                "[{}] {}",
+                if step.passed { "pass" } else { "fail" },
+                step.check
+            )?;
+            for (label, value) in &step.details {
+                write!(f, "\n    {}: {}", label, value)?;
+            }
+            writeln!(f)?;
+        }
+        write!(
+            f,
+            "result: {}",
+            if self.is_valid() { "valid" } else { "invalid" }
+        )
+    }
+}
+
+// Formats the cumulative sums of `values`, as shown in checksum steps.
+pub(crate) fn running_sum<T: Into<u64> + Copy>(values: &[T]) -> String {
+    let mut sum: u64 = 0;
+    let sums: Vec<u64> = values
+        .iter()
+        .map(|value| {
+            sum += (*value).into();
+            sum
+        })
+        .collect();
+    format!("{:?}", sums)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::explanation::{running_sum, Explanation, Step};
+
+    #[test]
+    fn explanation_is_valid_when_all_steps_pass() {
+        let mut explanation = Explanation::with_trace("1-2", "12", true);
+        assert!(!explanation.is_valid());
+        assert!(explanation.check(Step::new("length", true).detail("expected", 2)));
+        assert!(explanation.is_valid());
+        assert!(!explanation.check(Step::new("checksum", false)));
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "checksum");
+    }
+
+    #[test]
+    fn explanation_renders_as_text() {
+        let mut explanation = Explanation::with_trace("1-2", "12", true);
+        explanation.check(
+            Step::new("length", true)
+                .detail("expected", 2)
+                .detail("actual", 2),
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "input: \"1-2\"\nsanitized: \"12\"\n[pass] length\n    expected: 2\n    actual: 2\nresult: valid"
+        );
+    }
+
+    #[test]
+    fn untraced_explanation_skips_details() {
+        let mut explanation = Explanation::with_trace("1-2", "12", false);
+        assert!(explanation.check(
+            explanation
+                .step("length", true)
+                .detail_with("expected", || -> u32 { panic!() })
+        ));
+        assert!(explanation.is_valid());
+        assert!(explanation.steps[0].details.is_empty());
+        assert!(explanation.input.is_empty());
+
+        let mut explanation = Explanation::with_trace("1-2", "12", true);
+        explanation.check(
+            explanation
+                .step("length", true)
+                .detail_with("expected", || 2),
+        );
+        assert_eq!(explanation.steps[0].details[0].1, "2");
+    }
+
+    #[test]
+    fn running_sum_is_cumulative() {
+        assert_eq!("[1, 3, 6]", running_sum(&[1_u32, 2, 3]));
+    }
+}
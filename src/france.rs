@@ -0,0 +1,4 @@
+/*!
+ Helpers specific to the French social security number (NIR).
+*/
+pub use crate::validator::france::{nir_kind, NirKind};
@@ -0,0 +1,8 @@
+/*!
+ Helpers specific to German identity documents: the identity card (Personalausweis), the passport
+ and their machine readable zone.
+*/
+pub use crate::validator::germany::{
+    explain_document_number, parse_mrz, validate_id_card_number, validate_mrz_date,
+    validate_passport_number, DocumentKind, TravelDocument,
+};
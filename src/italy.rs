@@ -0,0 +1,6 @@
+/*!
+ Helpers specific to the Italian fiscal code (codice fiscale).
+*/
+pub use crate::validator::italy::{
+    base_code, birthplace, matches_name, name_code, surname_code, validate_birthplace, Birthplace,
+};
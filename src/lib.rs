@@ -1,13 +1,46 @@
+pub mod belgium;
+pub mod brazil;
+pub mod canada;
+mod consistency;
+mod corrections;
 pub mod country;
+pub mod denmark;
+mod error;
+mod explanation;
+pub mod france;
 mod generator;
+pub mod germany;
+pub mod italy;
+mod national_id;
+mod partial;
+mod place;
+pub mod portugal;
+pub mod spain;
+pub mod usa;
 mod validator;
 
+pub use consistency::{
+    verify_against, verify_against_with_name, ConsistencyReport, FieldCheck, Name,
+};
+pub use corrections::suggest_corrections;
+pub use error::ValidationError;
+pub use explanation::{Explanation, Step};
+pub use national_id::NationalId;
+pub use partial::{validate_partial, PartialStatus};
+pub use place::PlaceOfBirth;
+
+/**
+ Personal data encoded in an identifier.
+
+ `gender` is 'M' or 'F', 'X' for non-binary people in Mexico, or 'U' when the identifier was given
+ to someone whose gender was unknown.
+**/
 pub struct Citizen {
     pub gender: char,
     pub year_of_birth: i32,
     pub month_of_birth: Option<u8>,
     pub day_of_birth: Option<u8>,
-    pub place_of_birth: Option<String>,
+    pub place_of_birth: Option<PlaceOfBirth>,
 }
 
 pub fn validate_id(id: &str, country: country::Code) -> bool {
@@ -15,6 +48,19 @@ pub fn validate_id(id: &str, country: country::Code) -> bool {
     return country_validator.validate_id(id);
 }
 
+pub fn explain(id: &str, country: country::Code) -> Explanation {
+    let country_validator = validator::get_validator(&country);
+    country_validator.explain(id)
+}
+
+pub fn validate_id_strict(id: &str, country: country::Code) -> bool {
+    let country_validator = validator::get_validator(&country);
+    match country_validator.sanitize_id_strict(id) {
+        Some(standard_id) => country_validator.validate_id(&standard_id),
+        None => false,
+    }
+}
+
 pub fn extract_information(id: &str, country: country::Code) -> Option<Citizen> {
     let country_validator = validator::get_validator(&country);
     return country_validator.extract_citizen(id);
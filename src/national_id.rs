@@ -0,0 +1,117 @@
+use crate::error::ValidationError;
+use crate::{country, validator, Citizen};
+use std::fmt;
+
+/**
+ A National Identification Number that is known to be valid for its country.
+
+ The identifier is stored in its canonical form (separators removed and upper cased), so two
+ spellings of the same number, such as "144-416-762.63" and "14441676263", compare as equal.
+**/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NationalId {
+    country: country::Code,
+    value: String,
+}
+
+impl NationalId {
+    pub fn parse(id: &str, country: country::Code) -> Result<NationalId, ValidationError> {
+        let country_validator = validator::get_validator(&country);
+        let value = country_validator.sanitize_id(id);
+        if value.is_empty() {
+            return Err(ValidationError::Empty);
+        }
+
+        if !country_validator.validate_id(id) {
+            return Err(ValidationError::Invalid);
+        }
+
+        Ok(NationalId { country, value })
+    }
+
+    pub fn country(&self) -> country::Code {
+        self.country
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn extract(&self) -> Option<Citizen> {
+        validator::get_validator(&self.country).extract_citizen(&self.value)
+    }
+
+    pub fn format(&self) -> String {
+        validator::get_validator(&self.country).format_id(&self.value)
+    }
+}
+
+impl fmt::Display for NationalId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl AsRef<str> for NationalId {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::country::Code;
+    use crate::error::ValidationError;
+    use crate::national_id::NationalId;
+    use std::collections::HashSet;
+
+    #[test]
+    fn parse_rejects_invalid_ids() {
+        assert_eq!(
+            NationalId::parse(" - ", Code::BR),
+            Err(ValidationError::Empty)
+        );
+        assert_eq!(
+            NationalId::parse("23294954040", Code::BR),
+            Err(ValidationError::Invalid)
+        );
+    }
+
+    #[test]
+    fn parse_normalizes_ids() {
+        let formatted = NationalId::parse("144-416-762.63", Code::BR).unwrap();
+        let plain = NationalId::parse("14441676263", Code::BR).unwrap();
+        assert_eq!(formatted, plain);
+        assert_eq!(formatted.value(), "14441676263");
+        assert_eq!(formatted.to_string(), "14441676263");
+
+        let mut ids = HashSet::new();
+        ids.insert(formatted);
+        ids.insert(plain);
+        assert_eq!(ids.len(), 1);
+    }
+
+    #[test]
+    fn same_value_in_different_countries_differs() {
+        // a valid Social Insurance Number in Canada and Social Security Number in the USA
+        let canada = NationalId::parse("046454286", Code::CA).unwrap();
+        let usa = NationalId::parse("046454286", Code::US).unwrap();
+        assert_eq!(canada.value(), usa.value());
+        assert_ne!(canada, usa);
+        assert!(canada.country() != usa.country());
+    }
+
+    #[test]
+    fn format_and_extract() {
+        let cpf = NationalId::parse("14441676263", Code::BR).unwrap();
+        assert_eq!(cpf.format(), "144.416.762-63");
+
+        let cpr = NationalId::parse(" 090792-1395 ", Code::DK).unwrap();
+        assert_eq!(cpr.format(), "090792-1395");
+        let citizen = cpr.extract().unwrap();
+        assert_eq!(citizen.year_of_birth, 1992);
+
+        let ssn = NationalId::parse("167381265", Code::US).unwrap();
+        assert_eq!(ssn.format(), "167-38-1265");
+    }
+}
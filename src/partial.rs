@@ -0,0 +1,136 @@
+use crate::{country, validator};
+
+/**
+ State of an identifier that is still being typed.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialStatus {
+    /// Every character typed so far fits the format, but the identifier is not complete yet.
+    Incomplete,
+    /// No identifier can start with what was typed, with the reason why.
+    InvalidPrefix(String),
+    /// The identifier has its full length and is valid or not.
+    Complete(bool),
+}
+
+pub fn validate_partial(prefix: &str, country: country::Code) -> PartialStatus {
+    let country_validator = validator::get_validator(&country);
+    let standard_prefix = country_validator.sanitize_id(prefix);
+    if let Err(reason) = country_validator.validate_prefix(&standard_prefix) {
+        return PartialStatus::InvalidPrefix(reason);
+    }
+
+    if standard_prefix.chars().count() < country_validator.layout().chars().count() {
+        return PartialStatus::Incomplete;
+    }
+
+    PartialStatus::Complete(country_validator.validate_id(&standard_prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::country::Code;
+    use crate::partial::{validate_partial, PartialStatus};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn empty_prefix_is_incomplete() {
+        for country in Code::iter() {
+            assert_eq!(PartialStatus::Incomplete, validate_partial("", country));
+        }
+    }
+
+    #[test]
+    fn valid_ids_are_complete() {
+        let ids = [
+            ("H71211672R", Code::AL),
+            ("1502957172694", Code::BA),
+            ("93.05.18-223.61", Code::BE),
+            ("7523169263", Code::BG),
+            ("144.416.762-63", Code::BR),
+            ("046 454 286", Code::CA),
+            ("86095742719", Code::DE),
+            ("090792-1395", Code::DK),
+            ("Y3338121F", Code::ES),
+            ("2820819398814 09", Code::FR),
+            ("MLLSNT82P65Z404U", Code::IT),
+            ("1983081246783", Code::LU),
+            ("JOIM890106HHGSMN08", Code::MX),
+            ("11084129 8 ZX8", Code::PT),
+            ("167-38-1265", Code::US),
+        ];
+        for (id, country) in ids {
+            assert_eq!(PartialStatus::Complete(true), validate_partial(id, country));
+            for end in 0..id.len() {
+                assert_eq!(
+                    PartialStatus::Incomplete,
+                    validate_partial(&id[..end], country)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn partial_ids() {
+        assert_eq!(
+            PartialStatus::Incomplete,
+            validate_partial("144.416", Code::BR)
+        );
+        assert_eq!(
+            PartialStatus::Complete(true),
+            validate_partial("144.416.762-63", Code::BR)
+        );
+        assert_eq!(
+            PartialStatus::Complete(false),
+            validate_partial("144.416.762-64", Code::BR)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("expected at most 11 characters".to_string()),
+            validate_partial("144.416.762-631", Code::BR)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("expected a digit at position 4, found 'A'".to_string()),
+            validate_partial("144A", Code::BR)
+        );
+    }
+
+    #[test]
+    fn partial_ids_use_format_knowledge() {
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid decade letter 'Z'".to_string()),
+            validate_partial("Z", Code::AL)
+        );
+        assert_eq!(
+            PartialStatus::Incomplete,
+            validate_partial("MRTMTT25D", Code::IT)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid month letter 'F'".to_string()),
+            validate_partial("MRTMTT25F", Code::IT)
+        );
+        assert_eq!(
+            PartialStatus::Incomplete,
+            validate_partial("AAIM9011", Code::MX)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid month '13'".to_string()),
+            validate_partial("AAIM9013", Code::MX)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid gender 'V'".to_string()),
+            validate_partial("AAIM901112V", Code::MX)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid day '4'".to_string()),
+            validate_partial("4", Code::DK)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid month '4'".to_string()),
+            validate_partial("09 4", Code::DK)
+        );
+        assert_eq!(
+            PartialStatus::InvalidPrefix("invalid area number '9'".to_string()),
+            validate_partial("9", Code::US)
+        );
+    }
+}
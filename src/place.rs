@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/**
+ Place of birth decoded from an identifier.
+
+ `code` is the place code exactly as encoded in the identifier and `name` its human readable name.
+ `subdivision` holds the ISO 3166-2 code of the region (e.g. "IT-MI") and `country` the ISO 3166
+ alpha-2 code of the country, when the identifier allows to tell them.
+
+ `names` holds the name of the place by ISO 639-1 language code, with at least English ("en")
+ and the language of the country issuing the identifier.
+
+ `approximate` is set when the identifier only hints at the place, such as the state where a
+ number was issued rather than where its holder was born.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceOfBirth {
+    pub code: String,
+    pub name: String,
+    pub subdivision: Option<String>,
+    pub country: Option<String>,
+    pub foreign_born: bool,
+    pub approximate: bool,
+    pub names: BTreeMap<String, String>,
+}
+
+impl PlaceOfBirth {
+    pub fn new(code: &str, name: &str) -> PlaceOfBirth {
+        PlaceOfBirth {
+            code: code.to_string(),
+            name: name.to_string(),
+            subdivision: None,
+            country: None,
+            foreign_born: false,
+            approximate: false,
+            names: BTreeMap::new(),
+        }
+    }
+
+    /**
+     Builds a place known only by its name, such as a birthplace declared by a person.
+    **/
+    pub fn named(name: &str) -> PlaceOfBirth {
+        PlaceOfBirth::new("", name)
+    }
+
+    pub fn subdivision(mut self, subdivision: &str) -> PlaceOfBirth {
+        self.subdivision = Some(subdivision.to_string());
+        self
+    }
+
+    pub fn country(mut self, country: &str) -> PlaceOfBirth {
+        self.country = Some(country.to_string());
+        self
+    }
+
+    pub fn foreign_born(mut self) -> PlaceOfBirth {
+        self.foreign_born = true;
+        self
+    }
+
+    pub fn approximate(mut self) -> PlaceOfBirth {
+        self.approximate = true;
+        self
+    }
+
+    pub fn translation(mut self, language: &str, name: &str) -> PlaceOfBirth {
+        self.names.insert(language.to_string(), name.to_string());
+        self
+    }
+
+    /**
+     Returns the name of the place in the given language, falling back to `name`.
+    **/
+    pub fn name_in(&self, language: &str) -> &str {
+        self.names.get(language).unwrap_or(&self.name)
+    }
+}
+
+impl fmt::Display for PlaceOfBirth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/**
+ Joins the names of the places an identifier may come from, as in "Quebec, Ontario or Yukon".
+**/
+pub(crate) fn join_names(names: Vec<&str>, conjunction: &str) -> String {
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} {} {}", rest.join(", "), conjunction, last),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::place::{join_names, PlaceOfBirth};
+
+    #[test]
+    fn builds_places() {
+        let place = PlaceOfBirth::new("F205", "MILANO (MI)")
+            .subdivision("IT-MI")
+            .country("IT");
+        assert_eq!(place.code, "F205");
+        assert_eq!(place.subdivision.as_deref(), Some("IT-MI"));
+        assert_eq!(place.country.as_deref(), Some("IT"));
+        assert!(!place.foreign_born);
+        assert!(!place.approximate);
+        assert_eq!(place.to_string(), "MILANO (MI)");
+        assert!(place.names.is_empty());
+
+        let place = place
+            .translation("it", "MILANO (MI)")
+            .translation("en", "MILAN (MI)");
+        assert_eq!(place.name_in("en"), "MILAN (MI)");
+        assert_eq!(place.name_in("it"), "MILANO (MI)");
+        assert_eq!(place.name_in("de"), "MILANO (MI)");
+
+        let place = PlaceOfBirth::named("Milano");
+        assert_eq!(place.code, "");
+        assert!(place.subdivision.is_none() && place.country.is_none());
+        assert!(
+            PlaceOfBirth::new("NE", "NACIDO EN EL EXTRANJERO")
+                .foreign_born()
+                .foreign_born
+        );
+        assert!(PlaceOfBirth::named("New York").approximate().approximate);
+    }
+
+    #[test]
+    fn joins_names() {
+        assert_eq!(join_names(vec!["Quebec"], "or"), "Quebec");
+        assert_eq!(join_names(vec!["Quebec", "Yukon"], "or"), "Quebec or Yukon");
+        assert_eq!(join_names(vec!["A", "B", "C"], "ou"), "A, B ou C");
+        assert_eq!(join_names(vec![], "or"), "");
+    }
+}
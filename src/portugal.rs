@@ -0,0 +1,7 @@
+/*!
+ Helpers specific to the Portuguese identification numbers (Cartão de Cidadão, NIF and NISS).
+*/
+pub use crate::validator::portugal::{
+    civil_id_number, explain_nif, explain_niss, nif_entity, reissue_count, validate_nif,
+    validate_niss, NifEntity,
+};
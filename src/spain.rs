@@ -0,0 +1,6 @@
+/*!
+ Helpers specific to the Spanish identification numbers (DNI, NIE, NIF and CIF).
+*/
+pub use crate::validator::spain::{
+    cif_entity, document_type, explain_cif, validate_cif, CifEntity, DocumentType,
+};
@@ -0,0 +1,6 @@
+/*!
+ Helpers specific to the United States Social Security Number and ITIN.
+*/
+pub use crate::validator::usa::{
+    issued_after_randomization, issuing_state, number_kind, validate_itin, NumberKind,
+};
@@ -1,4 +1,6 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
+use crate::validator::layout;
 use crate::{validator, Citizen};
 use chrono::NaiveDate;
 use regex::Regex;
@@ -14,20 +16,31 @@ Validation logic source: https://lookuptax.com/docs/tax-identification-number/al
 
 **/
 impl validator::CountryValidator for AlbaniaValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if !Regex::new(r"^[A-T]\d[0156]\d{6}[A-W]$")
-            .unwrap()
-            .is_match(&standard_id)
-        {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        let pattern = r"^[A-T]\d[0156]\d{6}[A-W]$";
+        if !explanation.check(
+            explanation
+                .step(
+                    "format",
+                    Regex::new(pattern).unwrap().is_match(&standard_id),
+                )
+                .detail("pattern", pattern),
+        ) {
+            return explanation;
         }
 
         let first_letter = standard_id[0..1].chars().nth(0).unwrap();
 
         let year_or_birth = extract_year_of_birth(standard_id.to_string());
-        if year_or_birth.is_none() {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("year of birth", year_or_birth.is_some())
+                .detail("decade letter", first_letter)
+                .detail("year digit", &standard_id[1..2]),
+        ) {
+            return explanation;
         }
 
         let gender = convert_gender(standard_id.to_string()).unwrap();
@@ -36,26 +49,69 @@ impl validator::CountryValidator for AlbaniaValidator {
 
         let date_of_birth =
             NaiveDate::from_ymd_opt(year_or_birth.unwrap(), month_of_birth, day_of_birth);
-        if date_of_birth.is_none() {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("date of birth", date_of_birth.is_some())
+                .detail_with("year", || year_or_birth.unwrap())
+                .detail("month", month_of_birth)
+                .detail("day", day_of_birth)
+                .detail("gender", gender),
+        ) {
+            return explanation;
         }
 
-        let mut sum = 0;
-        standard_id[1..9]
+        let weights: Vec<u32> = (1..=8).collect();
+        let mut products: Vec<u32> = standard_id[1..9]
             .chars()
-            .enumerate()
-            .for_each(|(idx, c)| sum += (c.to_digit(10).unwrap() as usize) * (idx + 1));
-        sum += convert_letter_table(first_letter).unwrap() as usize;
-        let expected_check_letter = convert_number_table(sum % 23);
-
-        expected_check_letter.is_some()
-            && expected_check_letter.unwrap() == standard_id[9..].chars().nth(0).unwrap()
+            .zip(weights.iter())
+            .map(|(c, weight)| c.to_digit(10).unwrap() * weight)
+            .collect();
+        products.push(convert_letter_table(first_letter).unwrap());
+        let sum: u32 = products.iter().sum();
+        let expected_check_letter = convert_number_table((sum % 23) as usize);
+        let check_letter = standard_id[9..].chars().nth(0).unwrap();
+
+        explanation.check(
+            explanation
+                .step(
+                    "check letter",
+                    expected_check_letter.is_some()
+                        && expected_check_letter.unwrap() == check_letter,
+                )
+                .detail_with("weights", || format!("{:?}", weights))
+                .detail("first letter value", products[8])
+                .detail_with("running sum", || running_sum(&products))
+                .detail("sum mod 23", sum % 23)
+                .detail_with("expected", || expected_check_letter.unwrap_or('?'))
+                .detail("actual", check_letter),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         Code::AL
     }
 
+    fn layout(&self) -> &'static str {
+        "LDDDDDDDDL"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        let chars: Vec<char> = prefix.chars().collect();
+        if !chars.is_empty() && !('A'..='T').contains(&chars[0]) {
+            return Err(format!("invalid decade letter '{}'", chars[0]));
+        }
+        if chars.len() > 2 && !['0', '1', '5', '6'].contains(&chars[2]) {
+            return Err(format!("invalid month '{}'", chars[2]));
+        }
+        layout::check_number(prefix, 4, 6, 1..=31, "day")?;
+        if chars.len() > 9 && !('A'..='W').contains(&chars[9]) {
+            return Err(format!("invalid check letter '{}'", chars[9]));
+        }
+        Ok(())
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self.validate_id(id) {
             return None;
@@ -250,4 +306,16 @@ mod tests {
         assert_eq!(12, c2.month_of_birth.unwrap());
         assert_eq!(11, c2.day_of_birth.unwrap());
     }
+
+    #[test]
+    fn al_explain() {
+        let validator = AlbaniaValidator;
+        let explanation = validator.explain("H71211672R");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "check letter");
+
+        let explanation = validator.explain("H71211672A");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "check letter");
+    }
 }
@@ -12,20 +12,24 @@ pub fn validate_luhn_10(code: &str) -> bool {
     return (sum % 10) == 0;
 }
 
-// https://en.wikipedia.org/wiki/Luhn_algorithm
 fn luhn_10_sum(code: &str) -> u32 {
-    let mut sum: u32 = 0;
+    luhn_10_values(code).iter().sum()
+}
+
+// https://en.wikipedia.org/wiki/Luhn_algorithm
+pub fn luhn_10_values(code: &str) -> Vec<u32> {
+    let mut values = Vec::new();
     let mut is_odd = (code.chars().count() % 2) != 0;
     for char in code.chars() {
         if is_odd {
-            sum += char.to_digit(36).unwrap()
+            values.push(char.to_digit(36).unwrap())
         } else {
             let digit = char.to_digit(36).unwrap() * 2;
-            sum += if digit > 9 { digit - 9 } else { digit }
+            values.push(if digit > 9 { digit - 9 } else { digit })
         }
         is_odd = !is_odd;
     }
-    return sum;
+    values
 }
 
 const VERHOEFF_DIHEDRAL: [[u8; 10]; 10] = [
@@ -66,22 +70,28 @@ pub fn compute_verhoeff_check_digit(code: &str) -> u32 {
     return VERHOEFF_INV[c] as u32;
 }
 
-// https://en.wikipedia.org/wiki/Verhoeff_algorithm
 fn verhoeff_check_digit(code: &str) -> usize {
+    return *verhoeff_interim_values(code).last().unwrap_or(&0);
+}
+
+// https://en.wikipedia.org/wiki/Verhoeff_algorithm
+pub fn verhoeff_interim_values(code: &str) -> Vec<usize> {
+    let mut values = Vec::new();
     let mut c: usize = 0;
-    for (idx, char) in code.chars().rev().into_iter().enumerate() {
+    for (idx, char) in code.chars().rev().enumerate() {
         let digit: usize = char.to_digit(36).unwrap() as usize;
         let p = VERHOEFF_PERMUTATION[idx % 8][digit] as usize;
         c = VERHOEFF_DIHEDRAL[c][p] as usize;
+        values.push(c);
     }
-    return c;
+    values
 }
 
 #[cfg(test)]
 mod tests {
     use crate::validator::algorithms::{
-        compute_luhn_10_check_digit, compute_verhoeff_check_digit, validate_luhn_10,
-        validate_verhoeff,
+        compute_luhn_10_check_digit, compute_verhoeff_check_digit, luhn_10_values,
+        validate_luhn_10, validate_verhoeff, verhoeff_interim_values,
     };
 
     #[test]
@@ -89,6 +99,10 @@ mod tests {
         assert_eq!(3, compute_luhn_10_check_digit("7992739871"));
         assert!(validate_luhn_10("79927398713"));
         assert!(validate_luhn_10("1983081246783"));
+        assert_eq!(
+            vec![7, 9, 9, 4, 7, 6, 9, 7, 7, 2, 3],
+            luhn_10_values("79927398713")
+        );
     }
 
     #[test]
@@ -96,5 +110,6 @@ mod tests {
         assert_eq!(3, compute_verhoeff_check_digit("236"));
         assert!(validate_verhoeff("2363"));
         assert_eq!(validate_verhoeff("5971654782313"), false);
+        assert_eq!(vec![3, 1, 4, 0], verhoeff_interim_values("2363"));
     }
 }
@@ -1,10 +1,40 @@
 use crate::country::Code;
+use crate::explanation::Explanation;
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
 use crate::{validator, Citizen};
-use chrono::{NaiveDate, Utc};
+use chrono::{Datelike, NaiveDate, Utc};
 use std::borrow::Borrow;
 
 pub(crate) struct BelgiumValidator;
 
+/**
+ Register a Belgian identification number belongs to.
+
+ Residents are listed in the National Register (NRN). People without residence who deal with
+ Belgian administrations get a number in the BIS register, where 40 is added to the birth month,
+ or 20 when their gender was unknown at registration.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    Nrn,
+    Bis,
+}
+
+/**
+ Returns the register a valid Belgian identification number belongs to.
+**/
+pub fn number_kind(id: &str) -> Option<NumberKind> {
+    let validator = BelgiumValidator;
+    if !validator.validate_id(id) {
+        return None;
+    }
+
+    get_kind(get_month_code(&validator.sanitize_id(id)))
+}
+
 /**
  Belgium National Identifier Number code validation.
 
@@ -13,50 +43,114 @@ pub(crate) struct BelgiumValidator;
  Another (english) version for the validation can be viewed in wikipedia: https://en.wikipedia.org/wiki/National_identification_number#Belgium
 **/
 impl validator::CountryValidator for BelgiumValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if standard_id.len() != 11 {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 11 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "11 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
         let checksum = standard_id.get(9..11).unwrap();
         let partial_id = standard_id.get(0..9).unwrap();
-        if !validate_checksum(partial_id, checksum) {
-            let id_for_2000 = "2".to_owned() + partial_id;
-
-            if !validate_checksum(id_for_2000.borrow(), checksum) {
-                return false;
-            }
-            return validate_date(("20".to_owned() + partial_id.get(0..6).unwrap()).borrow());
+        let id_for_2000 = "2".to_owned() + partial_id;
+        let born_before_2000 = validate_checksum(partial_id, checksum);
+        if !explanation.check(
+            explanation
+                .step(
+                    "checksum",
+                    born_before_2000 || validate_checksum(id_for_2000.borrow(), checksum),
+                )
+                .detail_with("expected before 2000", || expected_checksum(partial_id))
+                .detail_with("expected from 2000", || {
+                    expected_checksum(id_for_2000.borrow())
+                })
+                .detail("actual", checksum),
+        ) {
+            return explanation;
         }
 
-        return validate_date(("19".to_owned() + partial_id.get(0..6).unwrap()).borrow());
+        let date =
+            (if born_before_2000 { "19" } else { "20" }).to_owned() + partial_id.get(0..6).unwrap();
+        let month_code = get_month_code(&standard_id);
+        explanation.check(
+            explanation
+                .step(
+                    "date of birth",
+                    get_kind(month_code).is_some() && validate_date(date.borrow()),
+                )
+                .detail_with("kind", || format!("{:?}", get_kind(month_code)))
+                .detail_with("year", || date.get(0..4).unwrap())
+                .detail_with("month", || format!("{:02}", month_code % 20))
+                .detail_with("day", || date.get(6..8).unwrap()),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::BE;
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDDD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        if prefix.len() >= 4 && get_kind(get_month_code(prefix)).is_none() {
+            return Err(format!("invalid month '{}'", &prefix[2..4]));
+        }
+        layout::check_number(prefix, 4, 6, 0..=31, "day")
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[2, 4, 6, 9],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{}.{}.{}-{}.{}",
+            &standard_id[0..2],
+            &standard_id[2..4],
+            &standard_id[4..6],
+            &standard_id[6..9],
+            &standard_id[9..11]
+        )
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self::BelgiumValidator::validate_id(&self, id) {
             return None;
         }
 
-        let standard_id = id
-            .replace(" ", "")
-            .replace(".", "")
-            .replace("-", "")
-            .to_uppercase();
+        let standard_id = self.sanitize_id(id);
         let before2000 = validate_checksum(
             standard_id.get(0..9).unwrap(),
             standard_id.get(9..11).unwrap(),
         );
 
-        let gender = if (standard_id.get(6..9).unwrap().parse::<u64>().unwrap() % 2) == 0 {
+        let month_code = get_month_code(&standard_id);
+        // BIS numbers given to people whose gender was unknown add 20 to the month
+        let gender = if (20..=32).contains(&month_code) {
+            'U'
+        } else if (standard_id.get(6..9).unwrap().parse::<u64>().unwrap() % 2) == 0 {
             'F'
         } else {
             'M'
         };
+        let month = (month_code % 20) as u8;
+        let day = standard_id.get(4..6).unwrap().parse::<u8>().unwrap();
         let year = if before2000 {
             ("19".to_owned() + standard_id.get(0..2).unwrap())
                 .parse()
@@ -70,8 +164,8 @@ impl validator::CountryValidator for BelgiumValidator {
         return Some(Citizen {
             gender,
             year_of_birth: year,
-            month_of_birth: Some(standard_id.get(2..4).unwrap().parse::<u8>().unwrap()),
-            day_of_birth: Some(standard_id.get(4..6).unwrap().parse::<u8>().unwrap()),
+            month_of_birth: if month == 0 { None } else { Some(month) },
+            day_of_birth: if day == 0 { None } else { Some(day) },
             place_of_birth: None,
         });
     }
@@ -82,12 +176,32 @@ fn validate_checksum(id: &str, checksum: &str) -> bool {
     return checksum.parse::<u64>().unwrap() == calculated_checksum;
 }
 
+fn expected_checksum(id: &str) -> String {
+    format!("{:02}", 97 - (id.parse::<u64>().unwrap() % 97))
+}
+
+fn get_month_code(id: &str) -> u32 {
+    id.get(2..4).unwrap().parse().unwrap()
+}
+
+fn get_kind(month_code: u32) -> Option<NumberKind> {
+    match month_code {
+        0..=12 => Some(NumberKind::Nrn),
+        20..=32 | 40..=52 => Some(NumberKind::Bis),
+        _ => None,
+    }
+}
+
+// Dates are given in the NRN format, with a month of 00 or a day of 00 when they are unknown.
 fn validate_date(date: &str) -> bool {
-    let date_of_birth = NaiveDate::from_ymd_opt(
-        date.get(0..4).unwrap().parse().unwrap(),
-        date.get(4..6).unwrap().parse().unwrap(),
-        date.get(6..8).unwrap().parse().unwrap(),
-    );
+    let year: i32 = date.get(0..4).unwrap().parse().unwrap();
+    let month: u32 = date.get(4..6).unwrap().parse::<u32>().unwrap() % 20;
+    let day: u32 = date.get(6..8).unwrap().parse().unwrap();
+    if month == 0 || day == 0 {
+        return day <= 31 && year <= Utc::now().year();
+    }
+
+    let date_of_birth = NaiveDate::from_ymd_opt(year, month, day);
 
     if date_of_birth.is_some() {
         return date_of_birth.unwrap() <= Utc::now().naive_local().date();
@@ -97,6 +211,7 @@ fn validate_date(date: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use crate::validator::belgium::{number_kind, NumberKind};
     use crate::validator::CountryValidator;
 
     #[test]
@@ -159,4 +274,58 @@ mod tests {
         assert_eq!(citizen_kurt.month_of_birth.unwrap(), 9);
         assert_eq!(citizen_kurt.day_of_birth.unwrap(), 7);
     }
+
+    #[test]
+    fn be_bis_numbers() {
+        let validator = super::validator::belgium::BelgiumValidator;
+        assert_eq!(validator.validate_id("85.43.14-123.88"), true);
+        assert_eq!(validator.validate_id("85.23.14-124.44"), true);
+        assert_eq!(validator.validate_id("05.43.14-124.85"), true);
+        assert_eq!(validator.validate_id("85.13.14-123.72"), false); // month 13
+        assert_eq!(validator.validate_id("85.63.14-123.34"), false); // month 63
+        assert_eq!(validator.validate_id("85.42.31-123.56"), false); // 31 February
+
+        assert_eq!(number_kind("85.43.14-123.88"), Some(NumberKind::Bis));
+        assert_eq!(number_kind("93.05.18-223.61"), Some(NumberKind::Nrn));
+        assert_eq!(number_kind("93.05.18-223.62"), None);
+
+        let citizen = validator.extract_citizen("85.43.14-123.88").unwrap();
+        assert_eq!(citizen.gender, 'M');
+        assert_eq!(citizen.year_of_birth, 1985);
+        assert_eq!(citizen.month_of_birth, Some(3));
+        assert_eq!(citizen.day_of_birth, Some(14));
+
+        let citizen = validator.extract_citizen("85.23.14-124.44").unwrap();
+        assert_eq!(citizen.gender, 'U');
+        assert_eq!(citizen.month_of_birth, Some(3));
+
+        let citizen = validator.extract_citizen("05.43.14-124.85").unwrap();
+        assert_eq!(citizen.gender, 'F');
+        assert_eq!(citizen.year_of_birth, 2005);
+    }
+
+    #[test]
+    fn be_unknown_birth_dates() {
+        let validator = super::validator::belgium::BelgiumValidator;
+        assert_eq!(validator.validate_id("85.00.00-123.13"), true);
+        assert_eq!(validator.validate_id("85.40.00-125.97"), true);
+
+        let citizen = validator.extract_citizen("85.00.00-123.13").unwrap();
+        assert_eq!(citizen.year_of_birth, 1985);
+        assert!(citizen.month_of_birth.is_none());
+        assert!(citizen.day_of_birth.is_none());
+        assert_eq!(number_kind("85.40.00-125.97"), Some(NumberKind::Bis));
+    }
+
+    #[test]
+    fn be_explain() {
+        let validator = super::validator::belgium::BelgiumValidator;
+        let explanation = validator.explain("93.05.18-223.61");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "date of birth");
+
+        let explanation = validator.explain("93.05.18-223.62");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "checksum");
+    }
 }
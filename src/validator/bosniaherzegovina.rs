@@ -1,54 +1,86 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
 use crate::validator::date::get_year_of_birth;
+use crate::validator::layout;
 use crate::validator::CountryValidator;
-use crate::Citizen;
+use crate::{Citizen, PlaceOfBirth};
 use chrono::{Datelike, NaiveDate};
 use regex::Regex;
 
 pub(crate) struct BosniaHerzegovinaValidator;
 
+const WEIGHTS: [u32; 12] = [7, 6, 5, 4, 3, 2, 7, 6, 5, 4, 3, 2];
+
 /**
 * TODO: Find official source
 */
 impl CountryValidator for BosniaHerzegovinaValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if !Regex::new(r"^\d{13}$").unwrap().is_match(&standard_id) {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    Regex::new(r"^\d{13}$").unwrap().is_match(&standard_id),
+                )
+                .detail("expected", "13 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
         let region = standard_id[7..9].parse::<u32>().unwrap();
-        if (region < 10 || region > 19) && region != 1 {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("region", (10..=19).contains(&region) || region == 1)
+                .detail("expected", "01 or 10-19")
+                .detail("actual", &standard_id[7..9]),
+        ) {
+            return explanation;
         }
 
         let check_sum = standard_id[12..13].parse::<u32>().unwrap();
-        let mut sum = (7
-            * (standard_id[0..1].parse::<u32>().unwrap()
-                + standard_id[6..7].parse::<u32>().unwrap()))
-            + (6 * (standard_id[1..2].parse::<u32>().unwrap()
-                + standard_id[7..8].parse::<u32>().unwrap()))
-            + (5 * (standard_id[2..3].parse::<u32>().unwrap()
-                + standard_id[8..9].parse::<u32>().unwrap()))
-            + (4 * (standard_id[3..4].parse::<u32>().unwrap()
-                + standard_id[9..10].parse::<u32>().unwrap()))
-            + (3 * (standard_id[4..5].parse::<u32>().unwrap()
-                + standard_id[10..11].parse::<u32>().unwrap()))
-            + (2 * (standard_id[5..6].parse::<u32>().unwrap()
-                + standard_id[11..12].parse::<u32>().unwrap()));
+        let products: Vec<u32> = standard_id[0..12]
+            .chars()
+            .zip(WEIGHTS.iter())
+            .map(|(c, weight)| c.to_digit(10).unwrap() * weight)
+            .collect();
+        let mut sum: u32 = products.iter().sum();
 
         sum = 11 - (sum % 11);
         if sum == 10 || sum == 11 {
             sum = 0
         }
 
-        sum == check_sum
+        explanation.check(
+            explanation
+                .step("check digit", sum == check_sum)
+                .detail_with("weights", || format!("{:?}", WEIGHTS))
+                .detail_with("running sum", || running_sum(&products))
+                .detail("expected", sum)
+                .detail("actual", check_sum),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         Code::BA
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDDDDD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        layout::check_number(prefix, 7, 9, 1..=19, "region")?;
+        if prefix.len() >= 9 && (2..=9).contains(&prefix[7..9].parse::<u32>().unwrap()) {
+            return Err(format!("invalid region '{}'", &prefix[7..9]));
+        }
+        Ok(())
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self.validate_id(id) {
             return None;
@@ -166,11 +198,42 @@ impl CountryValidator for BosniaHerzegovinaValidator {
             year_of_birth: date_of_birth.year(),
             month_of_birth: Some(date_of_birth.month() as u8),
             day_of_birth: Some(date_of_birth.day() as u8),
-            place_of_birth: region,
+            place_of_birth: region.map(|name| get_place_of_birth(&standard_id[7..9], &name)),
         })
     }
 }
 
+fn get_place_of_birth(code: &str, name: &str) -> PlaceOfBirth {
+    let mut place = PlaceOfBirth::new(code, name).translation("en", name);
+    if let Some(native) = get_native_region(code) {
+        place = place.translation("bs", native);
+    }
+    // only the foreigners (01) and the Bosnian regions (10 to 19) pass validation
+    match code {
+        "01" => place.foreign_born(),
+        _ => place.country("BA"),
+    }
+}
+
+// Bosnian names of the regions a Bosnian citizen number can be issued in.
+fn get_native_region(code: &str) -> Option<&'static str> {
+    let region = match code {
+        "01" => "stranac u Bosni i Hercegovini",
+        "10" => "Banja Luka - Bosna i Hercegovina",
+        "11" => "Bihać - Bosna i Hercegovina",
+        "12" => "Doboj - Bosna i Hercegovina",
+        "13" => "Goražde - Bosna i Hercegovina",
+        "14" => "Livno - Bosna i Hercegovina",
+        "15" => "Mostar - Bosna i Hercegovina",
+        "16" => "Prijedor - Bosna i Hercegovina",
+        "17" => "Sarajevo - Bosna i Hercegovina",
+        "18" => "Tuzla - Bosna i Hercegovina",
+        "19" => "Zenica - Bosna i Hercegovina",
+        _ => return None,
+    };
+    Some(region)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::validator::bosniaherzegovina::BosniaHerzegovinaValidator;
@@ -216,7 +279,16 @@ mod tests {
         assert_eq!(1963, c1.year_of_birth);
         assert_eq!(10, c1.month_of_birth.unwrap());
         assert_eq!(13, c1.day_of_birth.unwrap());
-        assert_eq!("Livno - Bosnia and Herzegovina", c1.place_of_birth.unwrap());
+        let place_of_birth = c1.place_of_birth.unwrap();
+        assert_eq!("Livno - Bosnia and Herzegovina", place_of_birth.name);
+        assert_eq!("14", place_of_birth.code);
+        assert_eq!(Some("BA"), place_of_birth.country.as_deref());
+        assert!(place_of_birth.subdivision.is_none());
+        assert_eq!(
+            "Livno - Bosnia and Herzegovina",
+            place_of_birth.name_in("en")
+        );
+        assert_eq!("Livno - Bosna i Hercegovina", place_of_birth.name_in("bs"));
 
         let c2 = validator.extract_citizen("1806998154160").unwrap();
         assert_eq!('M', c2.gender);
@@ -225,7 +297,35 @@ mod tests {
         assert_eq!(18, c2.day_of_birth.unwrap());
         assert_eq!(
             "Mostar - Bosnia and Herzegovina",
-            c2.place_of_birth.unwrap()
+            c2.place_of_birth.unwrap().name
         );
     }
+
+    #[test]
+    fn ba_explain() {
+        let validator = BosniaHerzegovinaValidator {};
+        let explanation = validator.explain("1502957172694");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "check digit");
+
+        let explanation = validator.explain("2808928401264");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "region");
+    }
+
+    #[test]
+    fn ba_place_of_birth_bosnian_names() {
+        use super::get_place_of_birth;
+
+        for code in 10..=19 {
+            let place = get_place_of_birth(&code.to_string(), "region");
+            assert!(place.name_in("bs").ends_with(" - Bosna i Hercegovina"));
+            assert_eq!(Some("BA"), place.country.as_deref());
+        }
+        let place = get_place_of_birth("01", "foreigner in Bosnia and Herzegovina");
+        assert_eq!("stranac u Bosni i Hercegovini", place.name_in("bs"));
+        assert!(place.foreign_born);
+        let place = get_place_of_birth("83", "Unknown");
+        assert_eq!("Unknown", place.name_in("bs"));
+    }
 }
@@ -1,46 +1,305 @@
 use crate::country::Code;
-use crate::{validator, Citizen};
+use crate::explanation::{running_sum, Explanation};
+use crate::place::join_names;
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
+use crate::{validator, Citizen, PlaceOfBirth};
 
 pub(crate) struct BrazilValidator;
 
+pub(crate) struct CnpjValidator;
+
+const FIRST_WEIGHTS: [u32; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+const SECOND_WEIGHTS: [u32; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+const CNPJ_WEIGHTS: [u32; 13] = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/**
+ States of each fiscal region, by the 9th digit of the CPF, with their Portuguese and English names.
+**/
+const FISCAL_REGIONS: [&[(&str, &str, &str)]; 10] = [
+    &[("BR-RS", "Rio Grande do Sul", "Rio Grande do Sul")],
+    &[
+        ("BR-DF", "Distrito Federal", "Federal District"),
+        ("BR-GO", "Goiás", "Goiás"),
+        ("BR-MT", "Mato Grosso", "Mato Grosso"),
+        ("BR-MS", "Mato Grosso do Sul", "Mato Grosso do Sul"),
+        ("BR-TO", "Tocantins", "Tocantins"),
+    ],
+    &[
+        ("BR-PA", "Pará", "Pará"),
+        ("BR-AM", "Amazonas", "Amazonas"),
+        ("BR-AC", "Acre", "Acre"),
+        ("BR-AP", "Amapá", "Amapá"),
+        ("BR-RO", "Rondônia", "Rondônia"),
+        ("BR-RR", "Roraima", "Roraima"),
+    ],
+    &[
+        ("BR-CE", "Ceará", "Ceará"),
+        ("BR-MA", "Maranhão", "Maranhão"),
+        ("BR-PI", "Piauí", "Piauí"),
+    ],
+    &[
+        ("BR-PE", "Pernambuco", "Pernambuco"),
+        ("BR-RN", "Rio Grande do Norte", "Rio Grande do Norte"),
+        ("BR-PB", "Paraíba", "Paraíba"),
+        ("BR-AL", "Alagoas", "Alagoas"),
+    ],
+    &[("BR-BA", "Bahia", "Bahia"), ("BR-SE", "Sergipe", "Sergipe")],
+    &[("BR-MG", "Minas Gerais", "Minas Gerais")],
+    &[
+        ("BR-RJ", "Rio de Janeiro", "Rio de Janeiro"),
+        ("BR-ES", "Espírito Santo", "Espírito Santo"),
+    ],
+    &[("BR-SP", "São Paulo", "São Paulo")],
+    &[
+        ("BR-PR", "Paraná", "Paraná"),
+        ("BR-SC", "Santa Catarina", "Santa Catarina"),
+    ],
+];
+
+/**
+ Returns the states of the fiscal region where a valid CPF was issued, given by its 9th digit.
+ The place is approximate: it tells where the CPF was registered, which is not always where its
+ holder was born.
+**/
+pub fn fiscal_region(id: &str) -> Option<PlaceOfBirth> {
+    if !BrazilValidator.validate_id(id) {
+        return None;
+    }
+
+    let standard_id = BrazilValidator.sanitize_id(id);
+    let states = FISCAL_REGIONS[standard_id[8..9].parse::<usize>().unwrap()];
+    let name_pt = join_names(states.iter().map(|state| state.1).collect(), "ou");
+    let name_en = join_names(states.iter().map(|state| state.2).collect(), "or");
+
+    let place = PlaceOfBirth::new(&standard_id[8..9], &name_pt)
+        .country("BR")
+        .approximate()
+        .translation("pt", &name_pt)
+        .translation("en", &name_en);
+    if states.len() == 1 {
+        Some(place.subdivision(states[0].0))
+    } else {
+        Some(place)
+    }
+}
+
+/**
+ Validates a CNPJ, the identifier of Brazilian companies, in its numeric or alphanumeric form.
+**/
+pub fn validate_cnpj(id: &str) -> bool {
+    CnpjValidator.validate_id(id)
+}
+
+/**
+ Traces the checks performed on a CNPJ, see `explain`.
+**/
+pub fn explain_cnpj(id: &str) -> Explanation {
+    CnpjValidator.explain(id)
+}
+
+/**
+ Formats a CNPJ as `XX.XXX.XXX/XXXX-XX`, or returns `None` when it is not a valid CNPJ.
+**/
+pub fn format_cnpj(id: &str) -> Option<String> {
+    if !CnpjValidator.validate_id(id) {
+        return None;
+    }
+
+    Some(CnpjValidator.format_id(id))
+}
+
+// Characters of the alphanumeric CNPJ are worth their ASCII code minus 48, so digits keep their value.
+fn cnpj_check_digit(values: &[u32]) -> (Vec<u32>, u32) {
+    let weights = &CNPJ_WEIGHTS[CNPJ_WEIGHTS.len() - values.len()..];
+    let products: Vec<u32> = values
+        .iter()
+        .zip(weights.iter())
+        .map(|(value, weight)| value * weight)
+        .collect();
+    let remainder = products.iter().sum::<u32>() % 11;
+    let digit = if remainder < 2 { 0 } else { 11 - remainder };
+    (products, digit)
+}
+
 /**
  Brazil national id validation.
  Link: https://pt.wikipedia.org/wiki/Cadastro_de_pessoas_f%C3%ADsicas#Algoritmo
+
+ A CPF encodes neither the birth date nor the gender of its holder, so no citizen can be extracted.
+ The fiscal region where it was issued is available with `brazil::fiscal_region`.
 **/
 impl validator::CountryValidator for BrazilValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if standard_id.len() != 11 || !standard_id.chars().all(char::is_numeric) {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 11 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "11 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
-        let cpf = standard_id[0..(standard_id.len() - 2)].chars().rev();
-        let mut v1 = 0;
-        let mut v2 = 0;
-        for (idx, c) in cpf.enumerate() {
-            v1 = v1 + c.to_digit(32).unwrap() * (9 - (idx % 10)) as u32;
-            v2 = v2 + c.to_digit(32).unwrap() * (9 - ((idx + 1) % 10)) as u32;
-        }
+        let digits: Vec<u32> = standard_id[0..9]
+            .chars()
+            .map(|c| c.to_digit(10).unwrap())
+            .collect();
+        let first_products: Vec<u32> = digits
+            .iter()
+            .zip(FIRST_WEIGHTS.iter())
+            .map(|(digit, weight)| digit * weight)
+            .collect();
+        let mut second_products: Vec<u32> = digits
+            .iter()
+            .zip(SECOND_WEIGHTS.iter())
+            .map(|(digit, weight)| digit * weight)
+            .collect();
 
-        v1 = (v1 % 11) % 10;
-        v2 = v2 + (v1 * 9);
-        v2 = (v2 % 11) % 10;
+        let v1 = (first_products.iter().sum::<u32>() % 11) % 10;
+        second_products.push(v1 * SECOND_WEIGHTS[9]);
+        let v2 = (second_products.iter().sum::<u32>() % 11) % 10;
 
-        return standard_id[(standard_id.len() - 2)..]
-            .parse::<u32>()
-            .unwrap()
-            == ((v1 * 10) + v2);
+        let actual = &standard_id[9..];
+        if !explanation.check(
+            explanation
+                .step("first check digit", actual[0..1] == v1.to_string())
+                .detail_with("weights", || format!("{:?}", FIRST_WEIGHTS))
+                .detail_with("running sum", || running_sum(&first_products))
+                .detail("expected", v1)
+                .detail("actual", &actual[0..1]),
+        ) {
+            return explanation;
+        }
+
+        explanation.check(
+            explanation
+                .step("second check digit", actual[1..2] == v2.to_string())
+                .detail_with("weights", || format!("{:?}", SECOND_WEIGHTS))
+                .detail_with("running sum", || running_sum(&second_products))
+                .detail("expected", v2)
+                .detail("actual", &actual[1..2]),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::BR;
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDDD"
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[3, 6, 9],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{}.{}.{}-{}",
+            &standard_id[0..3],
+            &standard_id[3..6],
+            &standard_id[6..9],
+            &standard_id[9..11]
+        )
+    }
+
     fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
         return None;
     }
 }
 
+/**
+ Brazil company id (CNPJ) validation: 8 characters for the company, 4 for the branch and 2 check
+ digits computed with modulus 11. From July 2026 the first 12 characters may also be letters.
+ Link: https://www.gov.br/receitafederal/pt-br/acesso-a-informacao/acoes-e-programas/programas-e-atividades/cnpj-alfanumerico
+**/
+impl validator::CountryValidator for CnpjValidator {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
+        let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    layout::check_layout(&standard_id, self.layout()).is_ok()
+                        && standard_id.len() == 14,
+                )
+                .detail("expected", "12 letters or digits and 2 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let mut values: Vec<u32> = standard_id[0..12].chars().map(|c| c as u32 - 48).collect();
+        let (first_products, v1) = cnpj_check_digit(&values);
+        if !explanation.check(
+            explanation
+                .step("first check digit", standard_id[12..13] == v1.to_string())
+                .detail_with("weights", || format!("{:?}", &CNPJ_WEIGHTS[1..]))
+                .detail_with("running sum", || running_sum(&first_products))
+                .detail("expected", v1)
+                .detail("actual", &standard_id[12..13]),
+        ) {
+            return explanation;
+        }
+
+        values.push(v1);
+        let (second_products, v2) = cnpj_check_digit(&values);
+        explanation.check(
+            explanation
+                .step("second check digit", standard_id[13..14] == v2.to_string())
+                .detail_with("weights", || format!("{:?}", CNPJ_WEIGHTS))
+                .detail_with("running sum", || running_sum(&second_products))
+                .detail("expected", v2)
+                .detail("actual", &standard_id[13..14]),
+        );
+        explanation
+    }
+
+    fn country_code(&self) -> Code {
+        Code::BR
+    }
+
+    fn layout(&self) -> &'static str {
+        "AAAAAAAAAAAADD"
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separators: &[' ', '-', '.', '/'],
+            separator_positions: &[2, 5, 8, 12],
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{}.{}.{}/{}-{}",
+            &standard_id[0..2],
+            &standard_id[2..5],
+            &standard_id[5..8],
+            &standard_id[8..12],
+            &standard_id[12..14]
+        )
+    }
+
+    fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::validator::CountryValidator;
@@ -87,4 +346,80 @@ mod tests {
         let validator = super::validator::brazil::BrazilValidator;
         assert_eq!(validator.extract_citizen("17758534112").is_none(), true);
     }
+
+    #[test]
+    fn br_explain() {
+        let validator = super::validator::brazil::BrazilValidator;
+        let explanation = validator.explain("144-416-762.63");
+        assert!(explanation.is_valid());
+        assert_eq!(
+            explanation.steps.last().unwrap().check,
+            "second check digit"
+        );
+
+        let explanation = validator.explain("23294954040");
+        assert!(!explanation.is_valid());
+        assert_eq!(
+            explanation.failed_step().unwrap().check,
+            "second check digit"
+        );
+    }
+
+    #[test]
+    fn br_fiscal_region() {
+        use crate::validator::brazil::fiscal_region;
+
+        let place = fiscal_region("038.608.817-95").unwrap();
+        assert_eq!(place.code, "7");
+        assert_eq!(place.name, "Rio de Janeiro ou Espírito Santo");
+        assert_eq!(place.name_in("en"), "Rio de Janeiro or Espírito Santo");
+        assert!(place.subdivision.is_none());
+        assert!(place.approximate);
+
+        assert_eq!(fiscal_region("144-416-762.63").unwrap().code, "2");
+
+        let place = fiscal_region("26363102820").unwrap();
+        assert_eq!(place.name, "São Paulo");
+        assert_eq!(place.subdivision.as_deref(), Some("BR-SP"));
+        assert_eq!(place.country.as_deref(), Some("BR"));
+
+        assert_eq!(
+            fiscal_region("17758534112").unwrap().name_in("en"),
+            "Federal District, Goiás, Mato Grosso, Mato Grosso do Sul or Tocantins"
+        );
+        assert!(fiscal_region("23294954040").is_none());
+    }
+
+    #[test]
+    fn br_cnpj() {
+        use crate::validator::brazil::{explain_cnpj, format_cnpj, validate_cnpj};
+
+        assert!(validate_cnpj("11.222.333/0001-81"));
+        assert!(validate_cnpj("11222333000181"));
+        assert!(validate_cnpj("12.ABC.345/01DE-35"));
+        assert!(validate_cnpj("12abc34501de35"));
+        assert!(!validate_cnpj("11.222.333/0001-82"));
+        assert!(!validate_cnpj("12.ABC.345/01DE-3A"));
+        assert!(!validate_cnpj("1122233300018"));
+        assert!(!validate_cnpj("12.AB#.345/01DE-35"));
+
+        assert_eq!(
+            format_cnpj("11222333000181").as_deref(),
+            Some("11.222.333/0001-81")
+        );
+        assert_eq!(
+            format_cnpj("12abc34501de35").as_deref(),
+            Some("12.ABC.345/01DE-35")
+        );
+        assert_eq!(format_cnpj("123"), None);
+        assert_eq!(format_cnpj("11.222.333/0001-82"), None);
+        assert_eq!(format_cnpj("1122233300018é"), None);
+        assert_eq!(format_cnpj("ééééééééééééééé"), None);
+
+        let explanation = explain_cnpj("11.222.333/0001-80");
+        assert_eq!(
+            explanation.failed_step().unwrap().check,
+            "second check digit"
+        );
+    }
 }
@@ -1,4 +1,6 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
+use crate::validator::sanitizer::SanitizeRules;
 use crate::validator::CountryValidator;
 use crate::Citizen;
 use chrono::{Datelike, NaiveDate};
@@ -6,34 +8,61 @@ use regex::Regex;
 
 pub(crate) struct BulgariaValidator;
 
+const WEIGHTS: [u32; 9] = [2, 4, 8, 5, 10, 9, 7, 3, 6];
+
 /**
 * TODO: Find official docs on validation
 */
 impl CountryValidator for BulgariaValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if !Regex::new(r"^\d{10}$").unwrap().is_match(&standard_id) {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    Regex::new(r"^\d{10}$").unwrap().is_match(&standard_id),
+                )
+                .detail("expected", "10 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
-        let sum = (2 * standard_id[0..1].parse::<u32>().unwrap())
-            + (4 * standard_id[1..2].parse::<u32>().unwrap())
-            + (8 * standard_id[2..3].parse::<u32>().unwrap())
-            + (5 * standard_id[3..4].parse::<u32>().unwrap())
-            + (10 * standard_id[4..5].parse::<u32>().unwrap())
-            + (9 * standard_id[5..6].parse::<u32>().unwrap())
-            + (7 * standard_id[6..7].parse::<u32>().unwrap())
-            + (3 * standard_id[7..8].parse::<u32>().unwrap())
-            + (6 * standard_id[8..9].parse::<u32>().unwrap());
+        let products: Vec<u32> = standard_id[0..9]
+            .chars()
+            .zip(WEIGHTS.iter())
+            .map(|(c, weight)| c.to_digit(10).unwrap() * weight)
+            .collect();
+        let sum: u32 = products.iter().sum();
 
         let check_digit = standard_id[9..10].parse::<u32>().unwrap();
-        check_digit == (sum % 11)
+        explanation.check(
+            explanation
+                .step("check digit", check_digit == (sum % 11))
+                .detail_with("weights", || format!("{:?}", WEIGHTS))
+                .detail_with("running sum", || running_sum(&products))
+                .detail("expected", sum % 11)
+                .detail("actual", check_digit),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         Code::BG
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDD"
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separators: &[' ', '-', '.', '/'],
+            separator_positions: &[6],
+        }
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self.validate_id(id) {
             return None;
@@ -123,4 +152,16 @@ mod tests {
         assert_eq!(1, c2.month_of_birth.unwrap());
         assert_eq!(1, c2.day_of_birth.unwrap());
     }
+
+    #[test]
+    fn bg_explain() {
+        let validator = BulgariaValidator;
+        let explanation = validator.explain("7523169263");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "check digit");
+
+        let explanation = validator.explain("7542021030");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "check digit");
+    }
 }
@@ -1,31 +1,153 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
+use crate::place::join_names;
 use crate::validator::algorithms;
-use crate::{validator, Citizen};
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
+use crate::{validator, Citizen, PlaceOfBirth};
 
 pub(crate) struct CanadaValidator;
 
+const PROVINCES: [(&str, &str, &str); 13] = [
+    ("CA-NS", "Nova Scotia", "Nouvelle-Écosse"),
+    ("CA-NB", "New Brunswick", "Nouveau-Brunswick"),
+    ("CA-PE", "Prince Edward Island", "Île-du-Prince-Édouard"),
+    (
+        "CA-NL",
+        "Newfoundland and Labrador",
+        "Terre-Neuve-et-Labrador",
+    ),
+    ("CA-QC", "Quebec", "Québec"),
+    ("CA-ON", "Ontario", "Ontario"),
+    ("CA-MB", "Manitoba", "Manitoba"),
+    ("CA-SK", "Saskatchewan", "Saskatchewan"),
+    ("CA-AB", "Alberta", "Alberta"),
+    (
+        "CA-NT",
+        "Northwest Territories",
+        "Territoires du Nord-Ouest",
+    ),
+    ("CA-NU", "Nunavut", "Nunavut"),
+    ("CA-BC", "British Columbia", "Colombie-Britannique"),
+    ("CA-YT", "Yukon", "Yukon"),
+];
+
+/**
+ Returns the province, or group of provinces, where a valid SIN was registered.
+
+ The first digit tells the region: 1 for the Atlantic provinces, 2 and 3 for Quebec, 4 and 5 for
+ Ontario, 6 for the Prairies and the northern territories and 7 for British Columbia and Yukon.
+ Temporary residents (9) are not tied to a province and return `None`.
+**/
+pub fn registration_province(id: &str) -> Option<PlaceOfBirth> {
+    if !CanadaValidator.validate_id(id) {
+        return None;
+    }
+
+    let standard_id = CanadaValidator.sanitize_id(id);
+    let subdivisions: &[&str] = match &standard_id[0..1] {
+        "1" => &["CA-NS", "CA-NB", "CA-PE", "CA-NL"],
+        "2" | "3" => &["CA-QC"],
+        "4" | "5" => &["CA-ON"],
+        "6" => &["CA-MB", "CA-SK", "CA-AB", "CA-NT", "CA-NU"],
+        "7" => &["CA-BC", "CA-YT"],
+        _ => return None,
+    };
+    let provinces: Vec<&(&str, &str, &str)> = PROVINCES
+        .iter()
+        .filter(|(code, _, _)| subdivisions.contains(code))
+        .collect();
+    let name_en = join_names(provinces.iter().map(|p| p.1).collect(), "or");
+    let name_fr = join_names(provinces.iter().map(|p| p.2).collect(), "ou");
+
+    let place = PlaceOfBirth::new(&standard_id[0..1], &name_en)
+        .country("CA")
+        .approximate()
+        .translation("en", &name_en)
+        .translation("fr", &name_fr);
+    if subdivisions.len() == 1 {
+        Some(place.subdivision(subdivisions[0]))
+    } else {
+        Some(place)
+    }
+}
+
+/**
+ Tells whether a valid SIN was given to a temporary resident. These numbers start with a 9 and
+ expire with the permit of their holder.
+**/
+pub fn is_temporary_resident(id: &str) -> Option<bool> {
+    if !CanadaValidator.validate_id(id) {
+        return None;
+    }
+
+    Some(CanadaValidator.sanitize_id(id).starts_with('9'))
+}
+
 /**
  Canada Social Insurance Number code validation.
 
  TODO - Add Official Source
 
  Another (english) version can be viewed in wikipedia: https://en.wikipedia.org/wiki/Social_Insurance_Number
+
+ A SIN encodes neither the birth date nor the gender of its holder, so no citizen can be extracted.
+ The province of registration is available with `canada::registration_province`.
 **/
 impl validator::CountryValidator for CanadaValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
 
-        if standard_id.len() != 9 {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 9 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "9 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
-        algorithms::validate_luhn_10(&standard_id)
+        let values = algorithms::luhn_10_values(&standard_id);
+        explanation.check(
+            explanation
+                .step("luhn checksum", algorithms::validate_luhn_10(&standard_id))
+                .detail_with("doubled digits", || format!("{:?}", values))
+                .detail_with("running sum", || running_sum(&values))
+                .detail("expected", "sum divisible by 10"),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         Code::CA
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDD"
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[3, 6],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{} {} {}",
+            &standard_id[0..3],
+            &standard_id[3..6],
+            &standard_id[6..9]
+        )
+    }
+
     fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
         None
     }
@@ -60,4 +182,51 @@ mod tests {
         assert_eq!(validator.validate_id("501 343 719"), true);
         assert_eq!(validator.validate_id("912 046 737"), true);
     }
+
+    #[test]
+    fn ca_explain() {
+        let validator = super::validator::canada::CanadaValidator;
+        let explanation = validator.explain("046 454 286");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "luhn checksum");
+
+        let explanation = validator.explain("123-456-7");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "length");
+    }
+
+    #[test]
+    fn ca_registration_province() {
+        use crate::validator::canada::{is_temporary_resident, registration_province};
+
+        let place = registration_province("130 692 544").unwrap();
+        assert!(place.approximate);
+        assert!(place.subdivision.is_none());
+        assert_eq!(place.country.as_deref(), Some("CA"));
+        assert_eq!(place.code, "1");
+        assert_eq!(
+            place.name,
+            "Nova Scotia, New Brunswick, Prince Edward Island or Newfoundland and Labrador"
+        );
+        assert!(registration_province("046 454 286").is_none());
+
+        let place = registration_province("501 343 719").unwrap();
+        assert_eq!(place.name, "Ontario");
+        assert_eq!(place.subdivision.as_deref(), Some("CA-ON"));
+
+        let place = registration_province("671 143 899").unwrap();
+        assert_eq!(
+            place.name,
+            "Manitoba, Saskatchewan, Alberta, Northwest Territories or Nunavut"
+        );
+        assert_eq!(
+            place.name_in("fr"),
+            "Manitoba, Saskatchewan, Alberta, Territoires du Nord-Ouest ou Nunavut"
+        );
+
+        assert!(registration_province("912 046 737").is_none());
+        assert_eq!(is_temporary_resident("912 046 737"), Some(true));
+        assert_eq!(is_temporary_resident("501 343 719"), Some(false));
+        assert_eq!(is_temporary_resident("512 917 638"), None);
+    }
 }
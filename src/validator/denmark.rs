@@ -1,11 +1,59 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
+use crate::validator::layout;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
 use crate::{validator, Citizen};
+use chrono::NaiveDate;
 use regex::Regex;
 
 pub(crate) struct DenmarkValidator;
 
 const MULTIPLIER: [u32; 10] = [4, 3, 2, 7, 6, 5, 4, 3, 2, 1];
 
+/**
+ A citizen extracted from a CPR number, together with whether the number satisfies modulus 11.
+**/
+pub struct CprNumber {
+    pub citizen: Citizen,
+    pub passes_modulus_11: bool,
+}
+
+/**
+ Validates a CPR number, waiving its modulus 11 check for people born on or after 1 October 2007.
+
+ Since 1 October 2007 the CPR office gives out numbers that do not satisfy modulus 11 for the birth
+ dates whose modulus 11 numbers have all been used. The list of those dates grows over time, so
+ any valid date of birth from that day on is accepted. `validate_id` applies the same rule.
+**/
+pub fn validate_id_without_modulus_11(id: &str) -> bool {
+    let standard_id = DenmarkValidator.sanitize_id(id);
+    Regex::new(r"^\d{10}$").unwrap().is_match(&standard_id)
+        && get_date_of_birth(&standard_id).is_some()
+        && (validate_checksum(&standard_id) || is_modulus_11_waived(&standard_id))
+}
+
+/**
+ Tells whether a CPR number satisfies modulus 11, or `None` when it is not valid even without it.
+**/
+pub fn passes_modulus_11(id: &str) -> Option<bool> {
+    if !validate_id_without_modulus_11(id) {
+        return None;
+    }
+
+    Some(validate_checksum(&DenmarkValidator.sanitize_id(id)))
+}
+
+/**
+ Extracts the citizen from a CPR number and tells whether the number satisfies modulus 11.
+**/
+pub fn extract_cpr(id: &str) -> Option<CprNumber> {
+    Some(CprNumber {
+        citizen: DenmarkValidator.extract_citizen(id)?,
+        passes_modulus_11: passes_modulus_11(id)?,
+    })
+}
+
 /**
  * National Id for Denmark.
  *
@@ -13,29 +61,85 @@ const MULTIPLIER: [u32; 10] = [4, 3, 2, 7, 6, 5, 4, 3, 2, 1];
  *  - https://en.wikipedia.org/wiki/Personal_identification_number_(Denmark)
  */
 impl validator::CountryValidator for DenmarkValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if !Regex::new(r"^\d{10}$").unwrap().is_match(&standard_id) {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    Regex::new(r"^\d{10}$").unwrap().is_match(&standard_id),
+                )
+                .detail("expected", "10 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
         let year = extract_year(&standard_id);
         let month = standard_id[2..4].parse::<u32>().unwrap();
         let day = standard_id[0..2].parse::<u32>().unwrap();
+        if !explanation.check(
+            explanation
+                .step("date of birth", self.is_date_valid(year, month, day))
+                .detail("century digit", &standard_id[6..7])
+                .detail("year", year)
+                .detail("month", month)
+                .detail("day", day),
+        ) {
+            return explanation;
+        }
 
-        return self.is_date_valid(year, month, day) && validate_checksum(&standard_id);
+        let products = checksum_products(&standard_id);
+        let waived = is_modulus_11_waived(&standard_id);
+        explanation.check(
+            explanation
+                .step("modulus 11", validate_checksum(&standard_id) || waived)
+                .detail("waived (born from 1 October 2007)", waived)
+                .detail_with("weights", || format!("{:?}", MULTIPLIER))
+                .detail_with("running sum", || running_sum(&products))
+                .detail_with("sum mod 11", || products.iter().sum::<u32>() % 11)
+                .detail("expected", 0),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         crate::country::Code::DK
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        layout::check_number(prefix, 0, 2, 1..=31, "day")?;
+        layout::check_number(prefix, 2, 4, 1..=12, "month")
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separators: &[' ', '-', '.', '/'],
+            separator_positions: &[6],
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!("{}-{}", &standard_id[0..6], &standard_id[6..10])
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
+        if !validate_id_without_modulus_11(id) {
+            return None;
+        }
+
         let standard_id = self.sanitize_id(id);
         let gender = if standard_id[9..].parse::<u32>().unwrap() % 2 == 0 {
-            'M'
-        } else {
             'F'
+        } else {
+            'M'
         };
         return Some(Citizen {
             gender,
@@ -47,6 +151,19 @@ impl validator::CountryValidator for DenmarkValidator {
     }
 }
 
+fn get_date_of_birth(id: &str) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(
+        extract_year(id) as i32,
+        id[2..4].parse().unwrap(),
+        id[0..2].parse().unwrap(),
+    )
+}
+
+// Numbers without modulus 11 are only given out to people born on or after 1 October 2007.
+fn is_modulus_11_waived(id: &str) -> bool {
+    get_date_of_birth(id) >= NaiveDate::from_ymd_opt(2007, 10, 1)
+}
+
 fn extract_year(id: &str) -> u32 {
     let century_code = &id[6..7].parse::<u32>().unwrap();
     let year_code = &id[4..6].parse::<u32>().unwrap();
@@ -71,14 +188,17 @@ fn extract_year(id: &str) -> u32 {
 }
 
 fn validate_checksum(id: &str) -> bool {
-    let mut sum: u32 = 0;
-    for (idx, digit) in id.chars().enumerate() {
-        sum += digit.to_digit(36).unwrap() * MULTIPLIER[idx];
-    }
-
+    let sum: u32 = checksum_products(id).iter().sum();
     return sum % 11 == 0;
 }
 
+fn checksum_products(id: &str) -> Vec<u32> {
+    id.chars()
+        .zip(MULTIPLIER.iter())
+        .map(|(digit, multiplier)| digit.to_digit(36).unwrap() * multiplier)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::validator::CountryValidator;
@@ -110,21 +230,89 @@ mod tests {
         assert_eq!(validator.validate_id("1504373068"), true);
         assert_eq!(validator.validate_id("1608881995"), true);
         assert_eq!(validator.validate_id("0404047094"), true);
+        assert_eq!(validator.validate_id("090792/1395"), true);
+        assert_eq!(validator.validate_id("090792\u{2013}1395"), true);
+    }
+
+    #[test]
+    fn dk_validator_strict_separators() {
+        let validator = super::validator::denmark::DenmarkValidator;
+        assert_eq!(
+            validator.sanitize_id_strict(" 090792-1395 "),
+            Some("0907921395".to_string())
+        );
+        assert_eq!(validator.sanitize_id_strict("0907-921395"), None);
+        assert_eq!(validator.sanitize_id_strict("0907921-395"), None);
     }
 
     #[test]
     fn dk_extractor() {
         let extractor = super::validator::denmark::DenmarkValidator;
         let mut citizen = extractor.extract_citizen("090792-1395").unwrap();
-        assert_eq!(citizen.gender, 'F');
+        assert_eq!(citizen.gender, 'M');
         assert_eq!(citizen.year_of_birth, 1992);
         assert_eq!(citizen.month_of_birth.unwrap(), 7);
         assert_eq!(citizen.day_of_birth.unwrap(), 9);
 
         citizen = extractor.extract_citizen("070593-0600").unwrap();
-        assert_eq!(citizen.gender, 'M');
+        assert_eq!(citizen.gender, 'F');
         assert_eq!(citizen.year_of_birth, 1993);
         assert_eq!(citizen.month_of_birth.unwrap(), 5);
         assert_eq!(citizen.day_of_birth.unwrap(), 7);
     }
+
+    #[test]
+    fn dk_explain() {
+        let validator = super::validator::denmark::DenmarkValidator;
+        let explanation = validator.explain("090792-1395");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "modulus 11");
+
+        let explanation = validator.explain("311101-0001");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "date of birth");
+    }
+
+    #[test]
+    fn dk_without_modulus_11() {
+        use crate::validator::denmark::{
+            extract_cpr, passes_modulus_11, validate_id_without_modulus_11,
+        };
+
+        assert!(validate_id_without_modulus_11("090792-1395"));
+        assert!(validate_id_without_modulus_11("011007-4001"));
+        assert!(!validate_id_without_modulus_11("300907-4001")); // born before 1 October 2007
+        assert!(!validate_id_without_modulus_11("230321-2454"));
+        assert!(!validate_id_without_modulus_11("311101-0001"));
+        assert!(!validate_id_without_modulus_11("123"));
+
+        assert_eq!(passes_modulus_11("090792-1395"), Some(true));
+        assert_eq!(passes_modulus_11("011007-4001"), Some(false));
+        assert_eq!(passes_modulus_11("230321-2454"), None);
+        assert_eq!(passes_modulus_11("311101-0001"), None);
+
+        let extractor = super::validator::denmark::DenmarkValidator;
+        assert!(extractor.validate_id("011007-4001"));
+        assert!(!extractor.validate_id("300907-4001"));
+        let citizen = extractor.extract_citizen("011007-4001").unwrap();
+        assert_eq!(citizen.year_of_birth, 2007);
+        assert_eq!(citizen.month_of_birth, Some(10));
+        assert_eq!(citizen.gender, 'M');
+        assert!(extractor.extract_citizen("230321-2454").is_none());
+
+        let cpr = extract_cpr("011007-4001").unwrap();
+        assert!(!cpr.passes_modulus_11);
+        assert_eq!(cpr.citizen.day_of_birth, Some(1));
+        assert!(extract_cpr("090792-1395").unwrap().passes_modulus_11);
+        assert!(extract_cpr("230321-2454").is_none());
+
+        let explanation = extractor.explain("011007-4001");
+        assert!(explanation.is_valid());
+        assert_eq!(
+            explanation.steps.last().unwrap().details[0].1,
+            "true".to_string()
+        );
+        assert!(extractor.extract_citizen("311101-0001").is_none());
+        assert!(extractor.extract_citizen("123").is_none());
+    }
 }
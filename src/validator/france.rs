@@ -1,9 +1,50 @@
 use crate::country::Code;
+use crate::explanation::Explanation;
 use crate::validator::date;
-use crate::{validator, Citizen};
+use crate::validator::layout;
+use crate::validator::regions;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
+use crate::{validator, Citizen, PlaceOfBirth};
 
 pub(crate) struct FranceValidator;
 
+/**
+ Class of a French social security number, given by its first digit.
+ Odd digits are given to men and even digits to women.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NirKind {
+    /// Definitive number of a person registered by INSEE (1 or 2).
+    Nir,
+    /// Number of a person born abroad whose registration is in progress (3 or 4).
+    Provisional,
+    /// Temporary number (NIA) given to foreigners until they are registered (7 or 8).
+    Temporary,
+}
+
+/**
+ Returns the class of a valid French social security number.
+**/
+pub fn nir_kind(id: &str) -> Option<NirKind> {
+    let validator = FranceValidator;
+    if !validator.validate_id(id) {
+        return None;
+    }
+
+    get_kind(validator.sanitize_id(id).chars().next().unwrap())
+}
+
+fn get_kind(digit: char) -> Option<NirKind> {
+    match digit {
+        '1' | '2' => Some(NirKind::Nir),
+        '3' | '4' => Some(NirKind::Provisional),
+        '7' | '8' => Some(NirKind::Temporary),
+        _ => None,
+    }
+}
+
 /**
  French national citizen card number validation.
 
@@ -15,74 +56,217 @@ pub(crate) struct FranceValidator;
  For the region codes, the source used was https://fr.wikipedia.org/wiki/Codes_g%C3%A9ographiques_de_la_France
 **/
 impl validator::CountryValidator for FranceValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if standard_id.len() != 15 {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step("length", standard_id.len() == 15)
+                .detail("expected", 15)
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
-        for char in standard_id.chars() {
-            if !char.is_digit(10) {
-                return false;
-            }
+        let format = self.validate_prefix(&standard_id);
+        if !explanation.check(
+            explanation
+                .step("format", format.is_ok())
+                .detail("expected", "digits only, or 2A/2B as department")
+                .detail_with("kind", || {
+                    format!("{:?}", standard_id.chars().next().and_then(get_kind))
+                })
+                .detail_with("error", || {
+                    format.err().unwrap_or_else(|| "none".to_string())
+                }),
+        ) {
+            return explanation;
         }
 
         let control_digit = standard_id.get(13..).unwrap().parse::<u64>().unwrap();
-        let partial_id = standard_id.get(0..13).unwrap().parse::<u64>().unwrap();
+        // Corsican departments are replaced by 19 (2A) and 18 (2B) to compute the key
+        let numeric_id = standard_id[0..13].replace("2A", "19").replace("2B", "18");
+        let partial_id = numeric_id.parse::<u64>().unwrap();
 
-        return control_digit == 97 - (partial_id % 97);
+        explanation.check(
+            explanation
+                .step("control key", control_digit == 97 - (partial_id % 97))
+                .detail("number", &numeric_id)
+                .detail("number mod 97", partial_id % 97)
+                .detail_with("expected", || format!("{:02}", 97 - (partial_id % 97)))
+                .detail_with("actual", || format!("{:02}", control_digit)),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::FR;
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDADDDDDDDD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        if let Some(digit) = prefix.chars().next() {
+            if get_kind(digit).is_none() {
+                return Err(format!("invalid first digit '{}'", digit));
+            }
+        }
+        match prefix.get(5..7) {
+            Some(department) if !department.chars().all(|c| c.is_ascii_digit()) => {
+                if department == "2A" || department == "2B" {
+                    Ok(())
+                } else {
+                    Err(format!("invalid department '{}'", department))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[1, 3, 5, 7, 10, 13],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{} {} {} {} {} {} {}",
+            &standard_id[0..1],
+            &standard_id[1..3],
+            &standard_id[3..5],
+            &standard_id[5..7],
+            &standard_id[7..10],
+            &standard_id[10..13],
+            &standard_id[13..15]
+        )
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self::FranceValidator::validate_id(&self, id) {
             return None;
         }
 
-        let region = get_region_of_birth(&id[5..7]);
+        let standard_id = self.sanitize_id(id);
+        let year_of_birth = date::get_year_of_birth(&standard_id[1..3]);
+        let month_of_birth = get_month_of_birth(&standard_id[3..5]);
+        let place_of_birth = match &standard_id[5..7] {
+            "99" => Some(get_foreign_place_of_birth(&standard_id[5..10])),
+            "97" | "98" => get_place_of_birth(&standard_id[5..8]),
+            department => get_place_of_birth(department),
+        };
         return Some(Citizen {
-            gender: if String::from(&id[0..1]).parse::<u8>().unwrap() == 1_u8 {
+            gender: if standard_id[0..1].parse::<u8>().unwrap() % 2 == 1 {
                 'M'
             } else {
                 'F'
             },
-            year_of_birth: date::get_year_of_birth(&id[1..3]),
-            month_of_birth: get_month_of_birth(&id[3..5]),
+            year_of_birth,
+            month_of_birth,
             day_of_birth: None,
-            place_of_birth: if region.is_some() {
-                region
-            } else {
-                get_region_of_birth(&id[5..8])
-            },
+            place_of_birth,
         });
     }
 }
 
+// INSEE gives fictitious months to people whose birth month is unknown: 20, 30 to 42 and 50 to 99.
+// They do not encode the real month, so only 01 to 12 are decoded.
+// Source: https://fr.wikipedia.org/wiki/Num%C3%A9ro_de_s%C3%A9curit%C3%A9_sociale_en_France
 fn get_month_of_birth(code: &str) -> Option<u8> {
     let month = code.parse::<u8>().unwrap();
-    return if month > 1_u8 && month < 13_u8 {
+    return if (1_u8..13_u8).contains(&month) {
         Some(month)
-    } else if month > 30_u8 && month < 43_u8 {
-        Some(month - 30_u8)
     } else {
         None
     };
 }
 
+fn get_place_of_birth(department: &str) -> Option<PlaceOfBirth> {
+    let name = get_region_of_birth(department)?;
+    // overseas collectivities have their own ISO 3166-2 codes, and Paris became "75C" in 2019
+    let subdivision = match department {
+        "20" => "20R".to_string(),
+        "75" => "75C".to_string(),
+        "975" => "PM".to_string(),
+        "977" => "BL".to_string(),
+        "978" => "MF".to_string(),
+        "984" => "TF".to_string(),
+        "986" => "WF".to_string(),
+        "987" => "PF".to_string(),
+        "988" => "NC".to_string(),
+        "989" => "CP".to_string(),
+        code => format!("{:0>2}", code),
+    };
+    // the table points overseas collectivities to their ISO 3166-1 code, which is not part of the name
+    let native_name = name.split(" (voir aussi").next().unwrap();
+    let english_name = match department {
+        "20" => "Corsica",
+        "973" => "French Guiana",
+        "974" => "Réunion",
+        "975" => "Saint Pierre and Miquelon",
+        "977" => "Saint Barthélemy",
+        "978" => "Saint Martin",
+        "984" => "French Southern and Antarctic Lands",
+        "986" => "Wallis and Futuna",
+        "987" => "French Polynesia",
+        "988" => "New Caledonia",
+        "989" => "Clipperton Island",
+        _ => native_name,
+    };
+    Some(
+        PlaceOfBirth::new(department, &name)
+            .subdivision(&format!("FR-{}", subdivision))
+            .country("FR")
+            .translation("fr", native_name)
+            .translation("en", english_name),
+    )
+}
+
+/**
+ People born abroad have department 99 followed by the INSEE code of their country of birth.
+ Countries missing from the INSEE table, such as the ones that no longer exist, are reported as
+ born abroad.
+**/
+fn get_foreign_place_of_birth(code: &str) -> PlaceOfBirth {
+    let country = regions::get_historical_regions(code, &regions::FRANCE_COUNTRIES)
+        .into_iter()
+        .next();
+
+    match country {
+        Some(country) => {
+            let english_name = country.name_en.as_deref().unwrap_or(&country.region);
+            let place = PlaceOfBirth::new(code, &country.region)
+                .foreign_born()
+                .translation("fr", &country.region)
+                .translation("en", english_name);
+            match country.iso_code.as_deref() {
+                Some(iso_code) => place.country(iso_code),
+                None => place,
+            }
+        }
+        None => PlaceOfBirth::new(code, "Étranger")
+            .foreign_born()
+            .translation("fr", "Étranger")
+            .translation("en", "Abroad"),
+    }
+}
+
 fn get_region_of_birth(code: &str) -> Option<String> {
     let region = match code {
-        "1" => "Ain",
-        "2" => "Aisne",
-        "3" => "Allier",
-        "4" => "Alpes-de-Haute-Provence",
-        "5" => "Hautes-Alpes",
-        "6" => "Alpes-Maritimes",
-        "7" => "Ardèche",
-        "8" => "Ardennes",
-        "9" => "Ariège",
+        "01" => "Ain",
+        "02" => "Aisne",
+        "03" => "Allier",
+        "04" => "Alpes-de-Haute-Provence",
+        "05" => "Hautes-Alpes",
+        "06" => "Alpes-Maritimes",
+        "07" => "Ardèche",
+        "08" => "Ardennes",
+        "09" => "Ariège",
         "10" => "Aube",
         "11" => "Aude",
         "12" => "Aveyron",
@@ -93,6 +277,9 @@ fn get_region_of_birth(code: &str) -> Option<String> {
         "17" => "Charente-Maritime",
         "18" => "Cher",
         "19" => "Corrèze",
+        "20" => "Corse",
+        "2A" => "Corse-du-Sud",
+        "2B" => "Haute-Corse",
         "21" => "Côte-d’Or",
         "22" => "Côtes-d’Armor",
         "23" => "Creuse",
@@ -193,7 +380,10 @@ fn get_region_of_birth(code: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use crate::validator::france::get_region_of_birth;
+    use crate::validator::france::{
+        get_foreign_place_of_birth, get_month_of_birth, get_place_of_birth, get_region_of_birth,
+        nir_kind, NirKind,
+    };
     use crate::validator::CountryValidator;
 
     #[test]
@@ -220,6 +410,123 @@ mod tests {
         assert_eq!(validator.validate_id("1820897401154 75"), true);
     }
 
+    #[test]
+    fn fr_validator_corsica() {
+        let validator = super::validator::france::FranceValidator;
+        assert_eq!(validator.validate_id("1 85 05 2A 004 123 56"), true);
+        assert_eq!(validator.validate_id("2 70 12 2b 033 045 88"), true);
+        assert_eq!(validator.validate_id("1 85 05 2B 004 123 56"), false);
+        assert_eq!(validator.validate_id("1 85 05 2C 004 123 56"), false);
+        assert_eq!(validator.validate_id("1 85 05 A2 004 123 56"), false);
+
+        let place_of_birth = validator
+            .extract_citizen("185052A00412356")
+            .unwrap()
+            .place_of_birth
+            .unwrap();
+        assert_eq!(place_of_birth.name, "Corse-du-Sud");
+        assert_eq!(place_of_birth.subdivision.as_deref(), Some("FR-2A"));
+    }
+
+    #[test]
+    fn fr_extractor_foreign_and_overseas_births() {
+        let validator = super::validator::france::FranceValidator;
+        let place_of_birth = validator
+            .extract_citizen("1900399109012 12")
+            .unwrap()
+            .place_of_birth
+            .unwrap();
+        assert!(place_of_birth.foreign_born);
+        assert_eq!(place_of_birth.code, "99109");
+        assert_eq!(place_of_birth.country.as_deref(), Some("DE"));
+        assert_eq!(place_of_birth.name_in("en"), "Germany");
+        assert!(place_of_birth.subdivision.is_none());
+
+        let place_of_birth = validator
+            .extract_citizen("1900399499012 49")
+            .unwrap()
+            .place_of_birth
+            .unwrap();
+        assert!(place_of_birth.foreign_born);
+        assert!(place_of_birth.country.is_none());
+        assert_eq!(place_of_birth.name_in("en"), "Abroad");
+
+        let place_of_birth = validator
+            .extract_citizen("2881197411005 16")
+            .unwrap()
+            .place_of_birth
+            .unwrap();
+        assert!(!place_of_birth.foreign_born);
+        assert_eq!(place_of_birth.code, "974");
+        assert_eq!(place_of_birth.name, "La Réunion");
+
+        let place_of_birth = validator
+            .extract_citizen("2850331025011 05")
+            .unwrap()
+            .place_of_birth
+            .unwrap();
+        assert_eq!(place_of_birth.name, "Haute-Garonne");
+    }
+
+    #[test]
+    fn fr_kinds() {
+        let validator = super::validator::france::FranceValidator;
+        assert_eq!(nir_kind("2820819398814 09"), Some(NirKind::Nir));
+        assert_eq!(nir_kind("3921275113004 09"), Some(NirKind::Provisional));
+        assert_eq!(nir_kind("7850599109012 86"), Some(NirKind::Temporary));
+        assert_eq!(nir_kind("2820819398814 08"), None);
+        assert_eq!(validator.validate_id("5850533063001 61"), false);
+
+        assert_eq!(
+            validator
+                .extract_citizen("3921275113004 09")
+                .unwrap()
+                .gender,
+            'M'
+        );
+        assert_eq!(
+            validator
+                .extract_citizen("4900399134001 93")
+                .unwrap()
+                .gender,
+            'F'
+        );
+        let citizen = validator.extract_citizen("7850599109012 86").unwrap();
+        assert_eq!(citizen.gender, 'M');
+        assert_eq!(
+            citizen.place_of_birth.unwrap().country.as_deref(),
+            Some("DE")
+        );
+        assert_eq!(
+            validator
+                .extract_citizen("8700133063001 97")
+                .unwrap()
+                .gender,
+            'F'
+        );
+    }
+
+    #[test]
+    fn fr_extractor_months() {
+        let validator = super::validator::france::FranceValidator;
+        let citizen = validator.extract_citizen("1920169123456 22").unwrap();
+        assert_eq!(citizen.month_of_birth, Some(1));
+        assert_eq!(
+            citizen.place_of_birth.unwrap().subdivision.as_deref(),
+            Some("FR-69")
+        );
+
+        let citizen = validator.extract_citizen("1602075056001 26").unwrap();
+        assert_eq!(citizen.year_of_birth, 1960);
+        assert!(citizen.month_of_birth.is_none());
+
+        assert_eq!(get_month_of_birth("01"), Some(1));
+        assert_eq!(get_month_of_birth("12"), Some(12));
+        for month in ["13", "20", "30", "31", "42", "50", "99"] {
+            assert_eq!(get_month_of_birth(month), None);
+        }
+    }
+
     #[test]
     fn fr_extractor_returns_none_for_invalid_id() {
         let validator = super::validator::france::FranceValidator;
@@ -237,21 +544,66 @@ mod tests {
         assert_eq!(citizen_annette.gender, 'F');
         assert_eq!(citizen_annette.year_of_birth, 1982);
         assert_eq!(citizen_annette.month_of_birth.unwrap(), 8);
-        assert_eq!(citizen_annette.place_of_birth.unwrap(), "Corrèze");
+        let place_of_birth = citizen_annette.place_of_birth.unwrap();
+        assert_eq!(place_of_birth.name, "Corrèze");
+        assert_eq!(place_of_birth.code, "19");
+        assert_eq!(place_of_birth.subdivision.as_deref(), Some("FR-19"));
+        assert_eq!(place_of_birth.name_in("en"), "Corrèze");
+        assert_eq!(place_of_birth.name_in("fr"), "Corrèze");
+        assert_eq!(place_of_birth.country.as_deref(), Some("FR"));
+        assert!(!place_of_birth.foreign_born);
 
         let citizen_lothair = validator.extract_citizen("1880858704571 57").unwrap();
         assert_eq!(citizen_lothair.gender, 'M');
         assert_eq!(citizen_lothair.year_of_birth, 1988);
         assert_eq!(citizen_lothair.month_of_birth.unwrap(), 8);
-        assert_eq!(citizen_lothair.place_of_birth.unwrap(), "Nièvre");
+        assert_eq!(citizen_lothair.place_of_birth.unwrap().name, "Nièvre");
+    }
+
+    #[test]
+    fn fr_place_names() {
+        let place_of_birth = get_place_of_birth("987").unwrap();
+        assert_eq!(place_of_birth.subdivision.as_deref(), Some("FR-PF"));
+        assert_eq!(place_of_birth.name_in("fr"), "Polynésie française");
+        assert_eq!(place_of_birth.name_in("en"), "French Polynesia");
+        assert!(get_place_of_birth("999").is_none());
+    }
+
+    #[test]
+    fn fr_foreign_place_names() {
+        let place_of_birth = get_foreign_place_of_birth("99216");
+        assert!(place_of_birth.foreign_born);
+        assert_eq!(place_of_birth.country.as_deref(), Some("CN"));
+        assert_eq!(place_of_birth.name_in("fr"), "Chine");
+        assert_eq!(place_of_birth.name_in("en"), "China");
+
+        let place_of_birth = get_foreign_place_of_birth("99415");
+        assert_eq!(place_of_birth.country.as_deref(), Some("AR"));
+        assert_eq!(place_of_birth.name, "Argentine");
+
+        let place_of_birth = get_foreign_place_of_birth("99999");
+        assert!(place_of_birth.country.is_none());
+        assert_eq!(place_of_birth.name_in("en"), "Abroad");
     }
 
     #[test]
     fn fr_get_region_code() {
         let unknown_region = get_region_of_birth("999");
-        let known_region = get_region_of_birth("1");
+        let known_region = get_region_of_birth("01");
         assert_eq!(unknown_region.is_none(), true);
         assert_eq!(known_region.is_some(), true);
         assert_eq!(known_region.unwrap(), "Ain");
     }
+
+    #[test]
+    fn fr_explain() {
+        let validator = super::validator::france::FranceValidator;
+        let explanation = validator.explain("2820819398814 09");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "control key");
+
+        let explanation = validator.explain("123X123X123dDAS");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "format");
+    }
 }
@@ -1,8 +1,227 @@
 use crate::country::Code;
+use crate::explanation::Explanation;
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
 use crate::{validator, Citizen};
+use chrono::{Datelike, NaiveDate, Utc};
 
 pub(crate) struct GermanyValidator;
 
+pub(crate) struct DocumentNumberValidator {
+    kind: DocumentKind,
+}
+
+const MRZ_WEIGHTS: [u32; 3] = [7, 3, 1];
+
+// Letters that may appear in German document numbers: no vowels and no B, D, Q or S.
+const DOCUMENT_CHARACTERS: &str = "CFGHJKLMNPRTVWXYZ0123456789";
+const ID_CARD_AUTHORITIES: &str = "LMNPRTVWXY";
+const PASSPORT_AUTHORITIES: &str = "CFGHJK";
+
+/**
+ German identity documents with a machine readable zone.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    IdCard,
+    Passport,
+}
+
+/**
+ Data read from the machine readable zone of a German identity card or passport.
+
+ `gender` is 'M', 'F', 'X' for diverse, or 'U' when the document does not state it. The date of
+ birth is `None` when the day or month of birth is unknown.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TravelDocument {
+    pub kind: DocumentKind,
+    pub document_number: String,
+    pub nationality: String,
+    pub gender: char,
+    pub date_of_birth: Option<NaiveDate>,
+    pub expiry_date: NaiveDate,
+}
+
+/**
+ Validates the number of a German identity card (Personalausweis): 9 letters or digits followed by
+ a 7-3-1 check digit, as printed in the machine readable zone.
+**/
+pub fn validate_id_card_number(id: &str) -> bool {
+    DocumentNumberValidator {
+        kind: DocumentKind::IdCard,
+    }
+    .validate_id(id)
+}
+
+/**
+ Validates the serial number of a German passport, with or without the 7-3-1 check digit found in
+ the machine readable zone.
+**/
+pub fn validate_passport_number(id: &str) -> bool {
+    DocumentNumberValidator {
+        kind: DocumentKind::Passport,
+    }
+    .validate_id(id)
+}
+
+/**
+ Traces the checks performed on an identity card or passport number, see `explain`.
+**/
+pub fn explain_document_number(id: &str, kind: DocumentKind) -> Explanation {
+    DocumentNumberValidator { kind }.explain(id)
+}
+
+/**
+ Validates a date printed in a machine readable zone as YYMMDD followed by its 7-3-1 check digit.
+**/
+pub fn validate_mrz_date(date: &str) -> bool {
+    date.len() == 7
+        && date.is_ascii()
+        && check_digit(&date[0..6]).is_some_and(|digit| date[6..7] == digit.to_string())
+        && NaiveDate::parse_from_str(&format!("20{}", &date[0..6]), "%Y%m%d").is_ok()
+}
+
+/**
+ Reads the machine readable zone of a German identity card (3 lines of 30 characters) or passport
+ (2 lines of 44 characters). The lines may be separated by line breaks or given as a single line.
+ Returns `None` when the zone is malformed or any of its check digits is wrong.
+**/
+pub fn parse_mrz(mrz: &str) -> Option<TravelDocument> {
+    let zone: String = mrz.split_whitespace().collect::<String>().to_uppercase();
+    if !zone.is_ascii() {
+        return None;
+    }
+
+    match zone.len() {
+        90 => parse_id_card_mrz(&zone[0..30], &zone[30..60]),
+        88 => parse_passport_mrz(&zone[0..44], &zone[44..88]),
+        _ => None,
+    }
+}
+
+fn parse_id_card_mrz(first: &str, second: &str) -> Option<TravelDocument> {
+    if !first.starts_with("IDD<<") {
+        return None;
+    }
+
+    let composite = format!(
+        "{}{}{}{}",
+        &first[5..30],
+        &second[0..7],
+        &second[8..15],
+        &second[18..29]
+    );
+    if !validate_id_card_number(&first[5..15]) || !has_check_digit(&composite, &second[29..30]) {
+        return None;
+    }
+
+    build_document(
+        DocumentKind::IdCard,
+        &first[5..14],
+        &second[0..7],
+        &second[7..8],
+        &second[8..15],
+        &second[15..18],
+    )
+}
+
+fn parse_passport_mrz(first: &str, second: &str) -> Option<TravelDocument> {
+    if !first.starts_with("P<D<<") {
+        return None;
+    }
+
+    let personal_number_valid = has_check_digit(&second[28..42], &second[42..43])
+        || second[28..43].chars().all(|c| c == '<');
+    let composite = format!("{}{}{}", &second[0..10], &second[13..20], &second[21..43]);
+    if !validate_passport_number(&second[0..10])
+        || !personal_number_valid
+        || !has_check_digit(&composite, &second[43..44])
+    {
+        return None;
+    }
+
+    build_document(
+        DocumentKind::Passport,
+        &second[0..9],
+        &second[13..20],
+        &second[20..21],
+        &second[21..28],
+        &second[10..13],
+    )
+}
+
+fn build_document(
+    kind: DocumentKind,
+    document_number: &str,
+    birth: &str,
+    gender: &str,
+    expiry: &str,
+    nationality: &str,
+) -> Option<TravelDocument> {
+    if !validate_mrz_date(expiry) || !has_check_digit(&birth[0..6], &birth[6..7]) {
+        return None;
+    }
+
+    let gender = match gender {
+        "M" => 'M',
+        "F" => 'F',
+        "X" => 'X',
+        "<" => 'U',
+        _ => return None,
+    };
+    Some(TravelDocument {
+        kind,
+        document_number: document_number.to_string(),
+        nationality: nationality.trim_end_matches('<').to_string(),
+        gender,
+        date_of_birth: get_date_of_birth(&birth[0..6]),
+        expiry_date: NaiveDate::parse_from_str(&format!("20{}", &expiry[0..6]), "%Y%m%d").ok()?,
+    })
+}
+
+// Years of birth later than the current year belong to the previous century.
+fn get_date_of_birth(date: &str) -> Option<NaiveDate> {
+    let year = date[0..2].parse::<i32>().ok()?;
+    let current_year = Utc::now().year();
+    let century = if 2000 + year > current_year {
+        1900
+    } else {
+        2000
+    };
+    NaiveDate::from_ymd_opt(
+        century + year,
+        date[2..4].parse::<u32>().ok()?,
+        date[4..6].parse::<u32>().ok()?,
+    )
+}
+
+fn has_check_digit(value: &str, digit: &str) -> bool {
+    check_digit(value).is_some_and(|expected| digit == expected.to_string())
+}
+
+// ICAO 9303 check digit: letters count from 10 (A) to 35 (Z) and fillers (<) as 0.
+fn check_digit(value: &str) -> Option<u32> {
+    let values = mrz_values(value)?;
+    Some(
+        values
+            .iter()
+            .zip(MRZ_WEIGHTS.iter().cycle())
+            .map(|(value, weight)| value * weight)
+            .sum::<u32>()
+            % 10,
+    )
+}
+
+fn mrz_values(value: &str) -> Option<Vec<u32>> {
+    value
+        .chars()
+        .map(|c| if c == '<' { Some(0) } else { c.to_digit(36) })
+        .collect()
+}
+
 /**
 Germany Social Security Number code validation.
 
@@ -21,22 +240,42 @@ from: Informations Technik Zentrum Bund
 title: Steueridentifikationsnummer (IdNr) nach § 139b AO; Informationen zur Berechnung gültiger Prüfziffern
 **/
 impl validator::CountryValidator for GermanyValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
 
-        if standard_id.len() != 11 || &standard_id[0..1] == "0" {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 11 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "11 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        if !explanation.check(
+            explanation
+                .step("first digit", &standard_id[0..1] != "0")
+                .detail("expected", "1-9")
+                .detail("actual", &standard_id[0..1]),
+        ) {
+            return explanation;
         }
 
         let mut r11 = 10;
         let mut r10;
-        let mut repeated = vec![0; 10];
+        let mut interim_values = vec![];
+        let mut repeated = [0; 10];
+        let mut consecutive = false;
         for (idx, char) in standard_id[0..10].chars().enumerate() {
             if idx > 1 {
                 let first = standard_id.chars().nth(idx - 2).unwrap();
                 let second = standard_id.chars().nth(idx - 1).unwrap();
                 if first == second && first == char {
-                    return false;
+                    consecutive = true;
                 }
             }
             repeated[char.to_digit(10).unwrap() as usize] += 1;
@@ -46,10 +285,19 @@ impl validator::CountryValidator for GermanyValidator {
             }
 
             r11 = (2 * r10) % 11;
+            interim_values.push(r11);
         }
 
-        if repeated.iter().max().unwrap() > &3 {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "repeated digits",
+                    !consecutive && repeated.iter().max().unwrap() <= &3,
+                )
+                .detail_with("digit counts", || format!("{:?}", repeated))
+                .detail("three consecutive equal digits", consecutive),
+        ) {
+            return explanation;
         }
 
         let mut expected_check_sum = 11 - r11;
@@ -57,13 +305,113 @@ impl validator::CountryValidator for GermanyValidator {
             expected_check_sum = 0;
         }
 
-        expected_check_sum == standard_id.chars().nth(10).unwrap().to_digit(10).unwrap()
+        let check_sum = standard_id.chars().nth(10).unwrap().to_digit(10).unwrap();
+        explanation.check(
+            explanation
+                .step("check digit", expected_check_sum == check_sum)
+                .detail_with("interim values", || format!("{:?}", interim_values))
+                .detail("expected", expected_check_sum)
+                .detail("actual", check_sum),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         Code::DE
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDDD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        layout::check_number(prefix, 0, 1, 1..=9, "first digit")
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[2, 5, 8],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
+        None
+    }
+}
+
+/**
+ German identity card and passport number validation.
+
+ The first character tells the issuing authority, followed by 8 letters or digits taken from an
+ alphabet without vowels. The 7-3-1 check digit is the one printed in the machine readable zone.
+ See https://de.wikipedia.org/wiki/Ausweisnummer and ICAO Doc 9303 part 3.
+**/
+impl validator::CountryValidator for DocumentNumberValidator {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
+        let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        let lengths: &[usize] = match self.kind {
+            DocumentKind::IdCard => &[10],
+            DocumentKind::Passport => &[9, 10],
+        };
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    lengths.contains(&standard_id.len()) && standard_id.is_ascii(),
+                )
+                .detail_with("expected", || format!("{:?}", lengths))
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let authorities = match self.kind {
+            DocumentKind::IdCard => ID_CARD_AUTHORITIES,
+            DocumentKind::Passport => PASSPORT_AUTHORITIES,
+        };
+        if !explanation.check(
+            explanation
+                .step(
+                    "format",
+                    authorities.contains(&standard_id[0..1])
+                        && standard_id[0..9]
+                            .chars()
+                            .all(|c| DOCUMENT_CHARACTERS.contains(c)),
+                )
+                .detail("first character", authorities)
+                .detail("characters", DOCUMENT_CHARACTERS),
+        ) {
+            return explanation;
+        }
+
+        if standard_id.len() == 9 {
+            return explanation;
+        }
+
+        let values = mrz_values(&standard_id[0..9]).unwrap();
+        let expected = check_digit(&standard_id[0..9]).unwrap();
+        explanation.check(
+            explanation
+                .step("check digit", standard_id[9..10] == expected.to_string())
+                .detail_with("weights", || format!("{:?}", MRZ_WEIGHTS))
+                .detail_with("values", || format!("{:?}", values))
+                .detail("expected", expected)
+                .detail("actual", &standard_id[9..10]),
+        );
+        explanation
+    }
+
+    fn country_code(&self) -> Code {
+        Code::DE
+    }
+
+    fn layout(&self) -> &'static str {
+        "LAAAAAAAAD"
+    }
+
     fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
         None
     }
@@ -108,4 +456,95 @@ mod tests {
         let validator = super::validator::germany::GermanyValidator;
         assert!(validator.extract_citizen("65929970489").is_none())
     }
+
+    #[test]
+    fn de_explain() {
+        let validator = super::validator::germany::GermanyValidator;
+        let explanation = validator.explain("86095742719");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "check digit");
+
+        let explanation = validator.explain("11145678908");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "repeated digits");
+    }
+
+    #[test]
+    fn de_document_numbers() {
+        use crate::validator::germany::{
+            explain_document_number, validate_id_card_number, validate_passport_number,
+            DocumentKind,
+        };
+
+        assert!(validate_id_card_number("T220001293"));
+        assert!(validate_id_card_number("t22000129 3"));
+        assert!(!validate_id_card_number("T220001294"));
+        assert!(!validate_id_card_number("T22000129"));
+        assert!(!validate_id_card_number("C01X00T478"));
+        assert!(!validate_id_card_number("TA2000129A"));
+
+        assert!(validate_passport_number("C01X00T478"));
+        assert!(validate_passport_number("C01X00T47"));
+        assert!(!validate_passport_number("C01X00T479"));
+        assert!(!validate_passport_number("T220001293"));
+
+        let explanation = explain_document_number("T220001294", DocumentKind::IdCard);
+        assert_eq!(explanation.failed_step().unwrap().check, "check digit");
+        let explanation = explain_document_number("B01X00T478", DocumentKind::Passport);
+        assert_eq!(explanation.failed_step().unwrap().check, "format");
+    }
+
+    #[test]
+    fn de_mrz_dates() {
+        use crate::validator::germany::validate_mrz_date;
+
+        assert!(validate_mrz_date("6408125"));
+        assert!(validate_mrz_date("2010315"));
+        assert!(!validate_mrz_date("6408126"));
+        assert!(!validate_mrz_date("6413125"));
+        assert!(!validate_mrz_date("640812"));
+    }
+
+    #[test]
+    fn de_parse_mrz() {
+        use crate::validator::germany::{parse_mrz, DocumentKind};
+        use chrono::NaiveDate;
+
+        let id_card = parse_mrz(
+            "IDD<<T220001293<<<<<<<<<<<<<<<\n6408125<2010315D<<<<<<<<<<<<<4\nMUSTERMANN<<ERIKA<<<<<<<<<<<<<",
+        )
+        .unwrap();
+        assert_eq!(id_card.kind, DocumentKind::IdCard);
+        assert_eq!(id_card.document_number, "T22000129");
+        assert_eq!(id_card.nationality, "D");
+        assert_eq!(id_card.gender, 'U');
+        assert_eq!(id_card.date_of_birth, NaiveDate::from_ymd_opt(1964, 8, 12));
+        assert_eq!(
+            id_card.expiry_date,
+            NaiveDate::from_ymd_opt(2020, 10, 31).unwrap()
+        );
+
+        let passport = parse_mrz(
+            "P<D<<MUSTERMANN<<ERIKA<<<<<<<<<<<<<<<<<<<<<<C01X00T478D<<6408125F2702283<<<<<<<<<<<<<<<4",
+        )
+        .unwrap();
+        assert_eq!(passport.kind, DocumentKind::Passport);
+        assert_eq!(passport.document_number, "C01X00T47");
+        assert_eq!(passport.gender, 'F');
+        assert_eq!(passport.date_of_birth, NaiveDate::from_ymd_opt(1964, 8, 12));
+        assert_eq!(
+            passport.expiry_date,
+            NaiveDate::from_ymd_opt(2027, 2, 28).unwrap()
+        );
+
+        assert!(parse_mrz(
+            "P<D<<MUSTERMANN<<ERIKA<<<<<<<<<<<<<<<<<<<<<<C01X00T478D<<6408125F2702283<<<<<<<<<<<<<<<5"
+        )
+        .is_none());
+        assert!(parse_mrz(
+            "IDD<<T220001293<<<<<<<<<<<<<<<6408125<2010316D<<<<<<<<<<<<<4MUSTERMANN<<ERIKA<<<<<<<<<<<<<"
+        )
+        .is_none());
+        assert!(parse_mrz("IDD<<T220001293").is_none());
+    }
 }
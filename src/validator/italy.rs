@@ -1,10 +1,22 @@
+use crate::consistency::Name;
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
 use crate::validator::date;
+use crate::validator::layout;
+use crate::validator::names;
 use crate::validator::regions;
-use crate::{validator, Citizen};
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
+use crate::{validator, Citizen, PlaceOfBirth};
+use chrono::NaiveDate;
+use std::str::FromStr;
 
 pub(crate) struct ItalyValidator;
 
+const OMOCODIA_LETTERS: &str = "LMNPQRSTUV";
+const OMOCODIA_POSITIONS: [usize; 7] = [6, 7, 9, 10, 12, 13, 14];
+
 /**
  Italy fiscal code validation.
 
@@ -17,48 +29,378 @@ pub(crate) struct ItalyValidator;
  https://web.archive.org/web/20160819012136/http://www.agenziaentrate.gov.it/wps/wcm/connect/321b0500426a5e2492629bc065cef0e8/codicicatastali_comuni_29_11_2010.pdf?MOD=AJPERES&CACHEID=321b500426a5e2492629bc065cef0e8
 **/
 impl validator::CountryValidator for ItalyValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if standard_id.len() != 16 {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step("length", standard_id.chars().count() == 16)
+                .detail("expected", 16)
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let format = self.validate_prefix(&standard_id);
+        if !explanation.check(
+            explanation
+                .step("format", format.is_ok())
+                .detail_with("layout", || self.layout())
+                .detail_with("omocodic", || {
+                    decode_omocodia(&standard_id).is_ok_and(|base| base != standard_id)
+                })
+                .detail_with("error", || {
+                    format.err().unwrap_or_else(|| "none".to_string())
+                }),
+        ) {
+            return explanation;
         }
 
         let mut is_odd = true;
-        let mut sum: u32 = 0;
+        let mut values: Vec<u32> = vec![];
         for char in standard_id[0..15].chars() {
             if is_odd {
-                sum += get_odd_char_value(char)
+                values.push(get_odd_char_value(char))
             } else {
-                sum += get_even_char_value(char) as u32
+                values.push(get_even_char_value(char) as u32)
             }
 
             is_odd = !is_odd;
         }
+        let sum: u32 = values.iter().sum();
 
         let control_letter = get_remainder_char(sum % 26).to_string();
-        return (standard_id[15..].parse::<String>().unwrap()) == control_letter;
+        explanation.check(
+            explanation
+                .step("control letter", standard_id[15..] == control_letter)
+                .detail_with("character values", || format!("{:?}", values))
+                .detail_with("running sum", || running_sum(&values))
+                .detail("sum mod 26", sum % 26)
+                .detail("expected", &control_letter)
+                .detail("actual", &standard_id[15..]),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::IT;
     }
 
+    fn encodes_name(&self) -> bool {
+        true
+    }
+
+    fn matches_name(&self, id: &str, name: &Name) -> bool {
+        let standard_id = self.sanitize_id(id);
+        let surname = format!(
+            "{} {}",
+            name.last_name,
+            name.second_last_name.clone().unwrap_or_default()
+        );
+        standard_id[0..3] == surname_code(&surname)
+            && standard_id[3..6] == name_code(&name.first_name)
+    }
+
+    fn layout(&self) -> &'static str {
+        "LLLLLLDDLDDLDDDL"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        let base_prefix = decode_omocodia(prefix)?;
+        layout::check_layout(&base_prefix, self.layout())?;
+        if base_prefix.len() > 8
+            && get_month_of_birth(base_prefix[8..9].parse::<char>().unwrap()).is_none()
+        {
+            return Err(format!("invalid month letter '{}'", &base_prefix[8..9]));
+        }
+        layout::check_number(&base_prefix, 9, 11, 1..=71, "day")?;
+        // women have 40 added to their day of birth, so days 32 to 40 are never used
+        if base_prefix.len() >= 11
+            && (32..=40).contains(&base_prefix[9..11].parse::<u32>().unwrap())
+        {
+            return Err(format!("invalid day '{}'", &base_prefix[9..11]));
+        }
+        Ok(())
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[3, 6, 11, 15],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self::ItalyValidator::validate_id(&self, id) {
             return None;
         }
 
+        let standard_id = decode_omocodia(&self.sanitize_id(id)).unwrap();
+        let place_of_birth = get_place_of_birth(&standard_id).map(|place| {
+            let place_of_birth = PlaceOfBirth::new(&place.code, &place.region)
+                .translation("it", &place.region)
+                .translation("en", place.name_en.as_ref().unwrap_or(&place.region));
+            if place.code.starts_with('Z') {
+                match place.iso_code {
+                    Some(iso_code) => place_of_birth.foreign_born().country(&iso_code),
+                    None => place_of_birth.foreign_born(),
+                }
+            } else {
+                match place.province {
+                    Some(province) => place_of_birth
+                        .subdivision(&format!("IT-{}", province))
+                        .country("IT"),
+                    None => place_of_birth.country("IT"),
+                }
+            }
+        });
         return Some(Citizen {
-            gender: get_gender(&id[9..11]),
-            year_of_birth: date::get_year_of_birth(&id[6..8]),
-            month_of_birth: get_month_of_birth(id[8..9].parse::<char>().unwrap()),
-            day_of_birth: Some(get_day_of_birth(&id[9..11])),
-            place_of_birth: regions::get_region_from_csv(
-                &id[11..15],
-                "./src/validator/regions/italy_regions.csv",
-            ), //italy_regions::get_region(&id[11..15]),
+            gender: get_gender(&standard_id[9..11]),
+            year_of_birth: date::get_year_of_birth(&standard_id[6..8]),
+            month_of_birth: get_month_of_birth(standard_id[8..9].parse::<char>().unwrap()),
+            day_of_birth: Some(get_day_of_birth(&standard_id[9..11])),
+            place_of_birth,
+        });
+    }
+}
+
+/**
+ Returns the base form of a valid fiscal code.
+
+ Codes issued to people who would otherwise share the same code (omocodia) have some of their
+ digits replaced by letters, starting from the right. The base form has those digits restored
+ and its control letter recalculated, so all variants of a code share the same base form.
+**/
+pub fn base_code(id: &str) -> Option<String> {
+    let validator = ItalyValidator;
+    if !validator.validate_id(id) {
+        return None;
+    }
+
+    let base = decode_omocodia(&validator.sanitize_id(id)).unwrap();
+    let control_letter = get_control_letter(&base[0..15]);
+    Some(format!("{}{}", &base[0..15], control_letter))
+}
+
+/**
+ Checks whether a valid fiscal code was derived from the given first name and surname.
+
+ People with several surnames should pass them all in `last_name`, separated by spaces.
+ Returns false for invalid codes.
+**/
+pub fn matches_name(id: &str, first_name: &str, last_name: &str) -> bool {
+    let validator = ItalyValidator;
+    validator.validate_id(id)
+        && validator.matches_name(
+            id,
+            &Name {
+                first_name: first_name.to_string(),
+                last_name: last_name.to_string(),
+                second_last_name: None,
+            },
+        )
+}
+
+/**
+ Place of birth encoded in a fiscal code.
+
+ People born in Italy are identified by their comune, people born abroad by the state they were
+ born in. Foreign states carry their ISO 3166 alpha-2 code when there is one: dissolved states use
+ their former code (e.g. "SU" for the Soviet Union), while territories without a code of their own,
+ such as the Channel Islands or the polar dependencies, have none. `country` is set when the crate
+ supports validating identifiers of that state.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Birthplace {
+    Comune {
+        code: String,
+        name: String,
+        province: String,
+    },
+    Foreign {
+        code: String,
+        name: String,
+        iso_code: Option<String>,
+        country: Option<Code>,
+    },
+}
+
+impl Birthplace {
+    pub fn is_foreign(&self) -> bool {
+        matches!(self, Birthplace::Foreign { .. })
+    }
+}
+
+/**
+ Returns the place of birth of a valid fiscal code, named as it was on the date of birth.
+**/
+pub fn birthplace(id: &str) -> Option<Birthplace> {
+    let validator = ItalyValidator;
+    if !validator.validate_id(id) {
+        return None;
+    }
+
+    let standard_id = decode_omocodia(&validator.sanitize_id(id)).unwrap();
+    let place = get_place_of_birth(&standard_id)?;
+    if place.code.starts_with('Z') {
+        let country = place
+            .iso_code
+            .as_deref()
+            .and_then(|iso_code| Code::from_str(iso_code).ok());
+        return Some(Birthplace::Foreign {
+            code: place.code,
+            name: place.region,
+            iso_code: place.iso_code,
+            country,
         });
     }
+
+    Some(Birthplace::Comune {
+        code: place.code,
+        name: place.region,
+        province: place.province.unwrap_or_default(),
+    })
+}
+
+/**
+ Validates a fiscal code and checks that its birthplace code was in use on the date of birth.
+
+ Comuni are merged, renamed and suppressed over time, and foreign states come and go. A code
+ referring to a place that did not exist yet, or no longer existed, when the citizen was born is
+ rejected. The bundled table dates the comuni suppressed by the mergers from 2009 to 2016 together
+ with the comuni created by them, and the foreign states created or dissolved since the First World
+ War. Comuni renamed without changing their code keep a single, current name.
+**/
+pub fn validate_birthplace(id: &str) -> bool {
+    let validator = ItalyValidator;
+    if !validator.validate_id(id) {
+        return false;
+    }
+
+    let standard_id = decode_omocodia(&validator.sanitize_id(id)).unwrap();
+    match get_date_of_birth(&standard_id) {
+        Some(date) => {
+            regions::get_region_at_date(&standard_id[11..15], date, &regions::ITALY_REGIONS)
+                .is_some()
+        }
+        None => false,
+    }
+}
+
+fn get_place_of_birth(standard_id: &str) -> Option<regions::HistoricalRegion> {
+    let places = regions::get_historical_regions(&standard_id[11..15], &regions::ITALY_REGIONS);
+    // prefer the name in use when the citizen was born, falling back to the latest one
+    let date_of_birth = get_date_of_birth(standard_id);
+    places
+        .iter()
+        .find(|place| date_of_birth.is_some_and(|date| place.is_valid_at(date)))
+        .or_else(|| places.last())
+        .cloned()
+}
+
+fn get_date_of_birth(standard_id: &str) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(
+        date::get_year_of_birth(&standard_id[6..8]),
+        get_month_of_birth(standard_id[8..9].parse::<char>().unwrap())? as u32,
+        get_day_of_birth(&standard_id[9..11]) as u32,
+    )
+}
+
+// Replaces the omocodic letters of a (possibly incomplete) code with the digits they stand for.
+fn decode_omocodia(id: &str) -> Result<String, String> {
+    let mut chars: Vec<char> = id.chars().collect();
+    let mut digit_found = false;
+    for position in OMOCODIA_POSITIONS.iter().rev() {
+        if *position >= chars.len() {
+            continue;
+        }
+
+        let c = chars[*position];
+        if c.is_ascii_digit() {
+            digit_found = true;
+            continue;
+        }
+
+        let digit = OMOCODIA_LETTERS.find(c);
+        if digit.is_none() {
+            return Err(format!(
+                "expected a digit at position {}, found '{}'",
+                position + 1,
+                c
+            ));
+        }
+        if digit_found {
+            return Err(format!(
+                "omocodic letter '{}' at position {} before a digit",
+                c,
+                position + 1
+            ));
+        }
+        chars[*position] = char::from_digit(digit.unwrap() as u32, 10).unwrap();
+    }
+
+    Ok(chars.into_iter().collect())
+}
+
+fn get_control_letter(id: &str) -> char {
+    let sum: u32 = id
+        .chars()
+        .enumerate()
+        .map(|(idx, c)| {
+            if idx % 2 == 0 {
+                get_odd_char_value(c)
+            } else {
+                get_even_char_value(c) as u32
+            }
+        })
+        .sum();
+    get_remainder_char(sum % 26)
+}
+
+/**
+ Returns the three letters a fiscal code derives from a surname.
+
+ The letters are the first three consonants of the surname, followed by its vowels when there are
+ not enough consonants, padded with 'X' for surnames shorter than three letters. Accents, spaces and
+ apostrophes are ignored, so "D'Àngelo" is coded as "DNG".
+**/
+pub fn surname_code(surname: &str) -> String {
+    let (consonants, vowels) = split_letters(surname);
+    consonants
+        .into_iter()
+        .chain(vowels)
+        .chain(std::iter::repeat('X'))
+        .take(3)
+        .collect()
+}
+
+/**
+ Returns the three letters a fiscal code derives from a first name.
+
+ Names with more than three consonants use the 1st, 3rd and 4th consonant. Otherwise the rules
+ are the same as for `surname_code`.
+**/
+pub fn name_code(name: &str) -> String {
+    let (consonants, vowels) = split_letters(name);
+    if consonants.len() > 3 {
+        return [consonants[0], consonants[2], consonants[3]]
+            .iter()
+            .collect();
+    }
+
+    consonants
+        .into_iter()
+        .chain(vowels)
+        .chain(std::iter::repeat('X'))
+        .take(3)
+        .collect()
+}
+
+fn split_letters(name: &str) -> (Vec<char>, Vec<char>) {
+    names::normalize_name(name)
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .partition(|c| !names::is_vowel(*c))
 }
 
 fn get_odd_char_value(character: char) -> u32 {
@@ -160,7 +502,11 @@ fn get_gender(day_of_birth: &str) -> char {
 
 #[cfg(test)]
 mod tests {
-    use crate::validator::italy::{get_even_char_value, get_odd_char_value};
+    use crate::consistency::Name;
+    use crate::validator::italy::{
+        base_code, birthplace, get_even_char_value, get_odd_char_value, matches_name, name_code,
+        surname_code, validate_birthplace, Birthplace,
+    };
     use crate::validator::CountryValidator;
 
     #[test]
@@ -193,6 +539,13 @@ mod tests {
         assert_eq!(validator.validate_id("ARSLGE02D50H987A"), false);
         assert_eq!(validator.validate_id("CSTDAM75B06C215T"), false);
         assert_eq!(validator.validate_id("ARLSNT66P65Z404R"), false);
+        assert_eq!(validator.validate_id("RSSMRA80A35F205N"), false); // day 35
+        assert_eq!(validator.validate_id("RSSMRA80A41F205B"), true);
+        assert!(validator.validate_prefix("RSSMRA80A3").is_ok());
+        assert_eq!(
+            validator.validate_prefix("RSSMRA80A35"),
+            Err("invalid day '35'".to_string())
+        );
     }
 
     #[test]
@@ -223,16 +576,182 @@ mod tests {
         assert_eq!(citizen_annette.year_of_birth, 2025);
         assert_eq!(citizen_annette.month_of_birth.unwrap(), 4);
         assert_eq!(citizen_annette.day_of_birth.unwrap(), 9);
-        assert_eq!(citizen_annette.place_of_birth.unwrap(), "MILANO (MI)");
+        let place_of_birth = citizen_annette.place_of_birth.unwrap();
+        assert_eq!(place_of_birth.name, "MILANO (MI)");
+        assert_eq!(place_of_birth.code, "F205");
+        assert_eq!(place_of_birth.subdivision.as_deref(), Some("IT-MI"));
+        assert_eq!(place_of_birth.name_in("en"), "MILAN (MI)");
+        assert_eq!(place_of_birth.name_in("it"), "MILANO (MI)");
+        assert_eq!(place_of_birth.country.unwrap(), "IT");
+        assert!(!place_of_birth.foreign_born);
 
         let citizen_lothair = validator.extract_citizen("MLLSNT82P65Z404U").unwrap();
         assert_eq!(citizen_lothair.gender, 'F');
         assert_eq!(citizen_lothair.year_of_birth, 1982);
         assert_eq!(citizen_lothair.month_of_birth.unwrap(), 9);
         assert_eq!(citizen_lothair.day_of_birth.unwrap(), 25);
+        let place_of_birth = citizen_lothair.place_of_birth.unwrap();
+        assert_eq!(place_of_birth.name, "STATI UNITI D'AMERICA");
+        assert_eq!(place_of_birth.name_in("en"), "UNITED STATES OF AMERICA");
+        assert!(place_of_birth.subdivision.is_none());
+        assert_eq!(place_of_birth.country.unwrap(), "US");
+        assert!(place_of_birth.foreign_born);
+    }
+
+    #[test]
+    fn it_explain() {
+        let validator = super::validator::italy::ItalyValidator;
+        let explanation = validator.explain("MRTMTT25D09F205Z");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "control letter");
+
+        let explanation = validator.explain("ARSLGE02D50H987A");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "control letter");
+    }
+
+    #[test]
+    fn it_name_codes() {
+        assert_eq!("MRT", surname_code("Moretti"));
+        assert_eq!("MTT", name_code("Matteo"));
+        assert_eq!("FOX", surname_code("Fo"));
+        assert_eq!("LUX", name_code("Lu"));
+        assert_eq!("GNN", name_code("Giovanni"));
+        assert_eq!("DLR", surname_code("De Lorenzo"));
+        assert_eq!("DNG", surname_code("D'Àngelo"));
+        assert_eq!("NCL", name_code("Nicolò"));
+        assert_eq!("XXX", surname_code(""));
+        assert_eq!("BDT", name_code("Benedetta Sofia"));
+        assert_eq!("REX", surname_code("Rè"));
+        assert_eq!("JNA", name_code("Jan"));
+    }
+
+    #[test]
+    fn it_matches_name_helper() {
+        assert!(matches_name("MRTMTT25D09F205Z", "Matteo", "Moretti"));
+        assert!(matches_name("mrtmtt nrdlv fnlrv", "matteo", "MORETTI"));
+        assert!(!matches_name("MRTMTT25D09F205Z", "Marco", "Moretti"));
+        assert!(!matches_name("MRTMTT25D09F205A", "Matteo", "Moretti"));
+    }
+
+    #[test]
+    fn it_matches_name() {
+        let validator = super::validator::italy::ItalyValidator;
+        let name = Name {
+            first_name: "Matteo".to_string(),
+            last_name: "Moretti".to_string(),
+            second_last_name: None,
+        };
+        assert!(validator.encodes_name());
+        assert!(validator.matches_name("MRTMTT25D09F205Z", &name));
+        assert!(!validator.matches_name("MLLSNT82P65Z404U", &name));
+    }
+
+    #[test]
+    fn it_validator_omocodic_ids() {
+        let validator = super::validator::italy::ItalyValidator;
+        assert_eq!(validator.validate_id("MRTMTT25D09F20RU"), true);
+        assert_eq!(validator.validate_id("MRTMTT25D09F2LRF"), true);
+        assert_eq!(validator.validate_id("MRTMTTNRDLVFNLRV"), true);
+        assert_eq!(validator.validate_id("MLLSNT82P65Z40QR"), true);
+        // letters other than L-V, and omocodic letters left of a digit
+        assert_eq!(validator.validate_id("MRTMTT25D09F20AU"), false);
+        assert_eq!(validator.validate_id("MRTMTT2RD09F205Z"), false);
+    }
+
+    #[test]
+    fn it_extractor_decodes_omocodic_ids() {
+        let validator = super::validator::italy::ItalyValidator;
+        let citizen = validator.extract_citizen("MRTMTTNRDLVFNLRV").unwrap();
+        assert_eq!(citizen.gender, 'M');
+        assert_eq!(citizen.year_of_birth, 2025);
+        assert_eq!(citizen.month_of_birth.unwrap(), 4);
+        assert_eq!(citizen.day_of_birth.unwrap(), 9);
+        assert_eq!(citizen.place_of_birth.unwrap().name, "MILANO (MI)");
+    }
+
+    #[test]
+    fn it_base_code() {
+        assert_eq!(base_code("MRTMTT25D09F205Z").unwrap(), "MRTMTT25D09F205Z");
+        assert_eq!(base_code("MRTMTT25D09F20RU").unwrap(), "MRTMTT25D09F205Z");
+        assert_eq!(base_code("mrtmtt nrdlv fnlrv").unwrap(), "MRTMTT25D09F205Z");
+        assert_eq!(base_code("MLLSNT82P65Z40QR").unwrap(), "MLLSNT82P65Z404U");
+        assert!(base_code("MRTMTT25D09F205A").is_none());
+    }
+
+    #[test]
+    fn it_validate_birthplace() {
+        assert!(validate_birthplace("MRTMTT25D09F205Z"));
+        assert!(validate_birthplace("RSSMRA80A01Z135P")); // URSS, 1980
+        assert!(!validate_birthplace("RSSMRA95A01Z135W")); // URSS, 1995
+        assert!(!validate_birthplace("RSSMRA85A01Z156Y")); // Czech Republic, 1985
+        assert!(validate_birthplace("RSSMRA95A01Z156A")); // Czech Republic, 1995
+        assert!(!validate_birthplace("MRTMTT25D09F205A"));
+        assert!(validate_birthplace("RSSMRA80A01A726I")); // Bazzano, 1980
+        assert!(!validate_birthplace("RSSMRA15A01A726U")); // Bazzano, merged into Valsamoggia in 2014
+        assert!(validate_birthplace("RSSMRA15A01M320I")); // Valsamoggia, 2015
+        assert!(!validate_birthplace("RSSMRA10A01M320D")); // Valsamoggia, 2010
+        assert!(validate_birthplace("RSSMRA70A01Z244P")); // South Vietnam, 1970
+        assert!(!validate_birthplace("RSSMRA80A01Z244R")); // South Vietnam, 1980
+        assert!(validate_birthplace("RSSMRA63A01Z350L")); // Tanganyika, 1963
+        assert!(!validate_birthplace("RSSMRA70A01Z350K")); // Tanganyika, 1970
+        assert!(validate_birthplace("RSSMRA00A01A260U")); // Amblar, 2000
+        assert!(!validate_birthplace("RSSMRA17A01A260A")); // Amblar, merged into Amblar-Don in 2016
+        assert!(!validate_birthplace("RSSMRA10A01M351F")); // Amblar-Don, 2010
+        assert!(validate_birthplace("RSSMRA17A01M351M")); // Amblar-Don, 2017
+        assert!(validate_birthplace("RSSMRA85A01Z118Y")); // Yugoslavia, 1985
+        assert!(!validate_birthplace("RSSMRA10A01Z118A")); // Yugoslavia, 2010
+        assert!(!validate_birthplace("RSSMRA85A01Z150K")); // Slovenia, 1985
+        assert!(validate_birthplace("RSSMRA95A01Z150M")); // Slovenia, 1995
+        assert!(validate_birthplace("RSSMRA35A01Z144F")); // Estonia, 1935
+        assert!(!validate_birthplace("RSSMRA80A01Z144M")); // Estonia, 1980
+        assert!(validate_birthplace("RSSMRA95A01Z144T")); // Estonia, 1995
+
+        let validator = super::validator::italy::ItalyValidator;
+        assert!(validator.validate_id("RSSMRA95A01Z135W"));
         assert_eq!(
-            citizen_lothair.place_of_birth.unwrap(),
-            "STATI UNITI D'AMERICA"
+            validator
+                .extract_citizen("RSSMRA95A01Z135W")
+                .unwrap()
+                .place_of_birth
+                .unwrap()
+                .name,
+            "URSS"
         );
     }
+
+    #[test]
+    fn it_birthplace() {
+        assert_eq!(
+            birthplace("MRTMTT25D09F205Z").unwrap(),
+            Birthplace::Comune {
+                code: "F205".to_string(),
+                name: "MILANO (MI)".to_string(),
+                province: "MI".to_string(),
+            }
+        );
+
+        let foreign = birthplace("MLLSNT82P65Z404U").unwrap();
+        assert!(foreign.is_foreign());
+        assert_eq!(
+            foreign,
+            Birthplace::Foreign {
+                code: "Z404".to_string(),
+                name: "STATI UNITI D'AMERICA".to_string(),
+                iso_code: Some("US".to_string()),
+                country: Some(crate::country::Code::US),
+            }
+        );
+
+        match birthplace("RSSMRA80A01Z135P").unwrap() {
+            Birthplace::Foreign {
+                iso_code, country, ..
+            } => {
+                assert_eq!(iso_code.unwrap(), "SU");
+                assert!(country.is_none());
+            }
+            _ => panic!("expected a foreign birthplace"),
+        }
+        assert!(birthplace("MRTMTT25D09F205A").is_none());
+    }
 }
@@ -0,0 +1,87 @@
+/**
+ Checks that a (possibly incomplete) identifier follows a layout.
+
+ Each character of the layout describes the expected character at that position:
+ `D` for a digit, `L` for a letter and `A` for either.
+**/
+pub fn check_layout(prefix: &str, layout: &str) -> Result<(), String> {
+    if prefix.chars().count() > layout.chars().count() {
+        return Err(format!(
+            "expected at most {} characters",
+            layout.chars().count()
+        ));
+    }
+
+    for (idx, (c, class)) in prefix.chars().zip(layout.chars()).enumerate() {
+        let (matches, expected) = match class {
+            'D' => (c.is_ascii_digit(), "a digit"),
+            'L' => (c.is_ascii_uppercase(), "a letter"),
+            _ => (c.is_ascii_alphanumeric(), "a letter or digit"),
+        };
+        if !matches {
+            return Err(format!(
+                "expected {} at position {}, found '{}'",
+                expected,
+                idx + 1,
+                c
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Checks that the digits at `start..end` of the prefix, if already typed, can still form a
+// number within `range`. A single typed digit is accepted if any completion fits.
+pub fn check_number(
+    prefix: &str,
+    start: usize,
+    end: usize,
+    range: std::ops::RangeInclusive<u32>,
+    field: &str,
+) -> Result<(), String> {
+    if prefix.len() <= start {
+        return Ok(());
+    }
+
+    let typed = &prefix[start..prefix.len().min(end)];
+    let missing = (end - start - typed.len()) as u32;
+    let low = typed.parse::<u32>().unwrap_or(0) * 10_u32.pow(missing);
+    let high = low + 10_u32.pow(missing) - 1;
+    if high < *range.start() || low > *range.end() {
+        return Err(format!("invalid {} '{}'", field, typed));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validator::layout::{check_layout, check_number};
+
+    #[test]
+    fn layout_checks_character_classes() {
+        assert!(check_layout("", "LDA").is_ok());
+        assert!(check_layout("A", "LDA").is_ok());
+        assert!(check_layout("A1B", "LDA").is_ok());
+        assert!(check_layout("A12", "LDA").is_ok());
+        assert_eq!(
+            check_layout("11", "LDA"),
+            Err("expected a letter at position 1, found '1'".to_string())
+        );
+        assert_eq!(
+            check_layout("A1B2", "LDA"),
+            Err("expected at most 3 characters".to_string())
+        );
+    }
+
+    #[test]
+    fn number_checks_partial_digits() {
+        assert!(check_number("99", 2, 4, 1..=12, "month").is_ok());
+        assert!(check_number("991", 2, 4, 1..=12, "month").is_ok());
+        assert!(check_number("9912", 2, 4, 1..=12, "month").is_ok());
+        assert!(check_number("992", 2, 4, 1..=12, "month").is_err());
+        assert!(check_number("9913", 2, 4, 1..=12, "month").is_err());
+        assert!(check_number("9900", 2, 4, 1..=12, "month").is_err());
+    }
+}
@@ -1,6 +1,11 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
 use crate::validator::algorithms;
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
 use crate::{validator, Citizen};
+use chrono::{NaiveDate, Utc};
 
 pub(crate) struct LuxembourgValidator;
 
@@ -10,27 +15,110 @@ pub(crate) struct LuxembourgValidator;
  TIN validation logic source: https://www.oecd.org/tax/automatic-exchange/crs-implementation-and-assistance/tax-identification-numbers/Luxembourg-TIN.pdf
 
  Another (english) version for the validation can be viewed in wikipedia: https://en.wikipedia.org/wiki/National_identification_number#Luxembourg
+
+ The number starts with the date of birth as YYYYMMDD, followed by a 3 digit serial number and the
+ two check digits. No gender convention is published for the serial number, so the gender of the
+ extracted citizen is unknown ('U').
 **/
 impl validator::CountryValidator for LuxembourgValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if standard_id.len() != 13 {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 13 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "13 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let date_of_birth = get_date_of_birth(&standard_id);
+        if !explanation.check(
+            explanation
+                .step(
+                    "date of birth",
+                    date_of_birth.is_some_and(|date| date <= Utc::now().naive_local().date()),
+                )
+                .detail("year", &standard_id[0..4])
+                .detail("month", &standard_id[4..6])
+                .detail("day", &standard_id[6..8]),
+        ) {
+            return explanation;
         }
-        if !algorithms::validate_luhn_10(&standard_id[0..12]) {
-            return false;
+
+        let luhn_values = algorithms::luhn_10_values(&standard_id[0..12]);
+        if !explanation.check(
+            explanation
+                .step(
+                    "luhn checksum",
+                    algorithms::validate_luhn_10(&standard_id[0..12]),
+                )
+                .detail("digits", &standard_id[0..12])
+                .detail_with("doubled digits", || format!("{:?}", luhn_values))
+                .detail_with("running sum", || running_sum(&luhn_values))
+                .detail("expected", "sum divisible by 10"),
+        ) {
+            return explanation;
         }
 
-        return algorithms::validate_verhoeff(&standard_id);
+        explanation.check(
+            explanation
+                .step(
+                    "verhoeff checksum",
+                    algorithms::validate_verhoeff(&standard_id),
+                )
+                .detail("digits", &standard_id)
+                .detail_with("interim values", || {
+                    format!("{:?}", algorithms::verhoeff_interim_values(&standard_id))
+                })
+                .detail("expected", "final interim value 0"),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::LU;
     }
 
-    fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
-        return None;
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDDDDD"
     }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        layout::check_number(prefix, 4, 6, 1..=12, "month")?;
+        layout::check_number(prefix, 6, 8, 1..=31, "day")
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[4, 8, 11],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn extract_citizen(&self, id: &str) -> Option<Citizen> {
+        if !self.validate_id(id) {
+            return None;
+        }
+
+        let standard_id = self.sanitize_id(id);
+        Some(Citizen {
+            gender: 'U',
+            year_of_birth: standard_id[0..4].parse::<i32>().unwrap(),
+            month_of_birth: Some(standard_id[4..6].parse::<u8>().unwrap()),
+            day_of_birth: Some(standard_id[6..8].parse::<u8>().unwrap()),
+            place_of_birth: None,
+        })
+    }
+}
+
+fn get_date_of_birth(id: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&id[0..8], "%Y%m%d").ok()
 }
 
 #[cfg(test)]
@@ -62,4 +150,42 @@ mod tests {
         assert_eq!(validator.validate_id("2012051469336"), true);
         assert_eq!(validator.validate_id("1994092874551"), true);
     }
+
+    #[test]
+    fn lu_explain() {
+        let validator = super::validator::luxembourg::LuxembourgValidator;
+        let explanation = validator.explain("1983081246783");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "verhoeff checksum");
+
+        let explanation = validator.explain("1983081256783");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "luhn checksum");
+    }
+
+    #[test]
+    fn lu_date_of_birth() {
+        let validator = super::validator::luxembourg::LuxembourgValidator;
+        let explanation = validator.explain("1994789587182");
+        assert_eq!(explanation.failed_step().unwrap().check, "date of birth");
+        let explanation = validator.explain("1983023046783");
+        assert_eq!(explanation.failed_step().unwrap().check, "date of birth");
+        assert!(validator.validate_prefix("198313").is_err());
+        assert!(validator.validate_prefix("19830832").is_err());
+        assert!(validator.validate_prefix("19830812").is_ok());
+    }
+
+    #[test]
+    fn lu_extractor() {
+        let validator = super::validator::luxembourg::LuxembourgValidator;
+        let citizen = validator.extract_citizen("1983081246783").unwrap();
+        assert_eq!(citizen.gender, 'U');
+        assert_eq!(citizen.year_of_birth, 1983);
+        assert_eq!(citizen.month_of_birth, Some(8));
+        assert_eq!(citizen.day_of_birth, Some(12));
+        assert!(citizen.place_of_birth.is_none());
+
+        assert!(validator.extract_citizen("1994789587182").is_none());
+        assert!(validator.extract_citizen("123").is_none());
+    }
 }
@@ -1,7 +1,12 @@
+use crate::consistency::Name;
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
 use crate::validator::date::get_year_of_birth;
+use crate::validator::layout;
+use crate::validator::names;
+use crate::validator::sanitizer::SanitizeRules;
 use crate::validator::words::get_inappropriate_words_mexico;
-use crate::{validator, Citizen};
+use crate::{validator, Citizen, PlaceOfBirth};
 use chrono::NaiveDate;
 
 pub(crate) struct MexicoValidator;
@@ -25,76 +30,180 @@ pub(crate) struct MexicoValidator;
  *  - (Inappropriate words) https://solucionfactible.com/sfic/resources/files/palabrasInconvenientes-rfc.pdf
 **/
 impl validator::CountryValidator for MexicoValidator {
-    fn validate_id(&self, id: &str) -> bool {
-        let standard_id = id.replace(" ", "").replace("-", "");
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
+        let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
 
-        if standard_id.len() != 18 || !standard_id[0..4].chars().all(|c| c.is_alphabetic()) {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("length", standard_id.len() == 18 && standard_id.is_ascii())
+                .detail("expected", 18)
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        if !explanation.check(
+            explanation
+                .step(
+                    "name initials",
+                    standard_id[0..4].chars().all(|c| c.is_alphabetic()),
+                )
+                .detail("actual", &standard_id[0..4]),
+        ) {
+            return explanation;
         }
 
         let month_of_birth = standard_id[6..8].parse::<u32>();
         let day_of_birth = standard_id[8..10].parse::<u32>();
 
-        if !standard_id[4..6].chars().all(|c| c.is_numeric())
-            || month_of_birth.is_err()
-            || day_of_birth.is_err()
-        {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "date format",
+                    standard_id[4..6].chars().all(|c| c.is_numeric())
+                        && month_of_birth.is_ok()
+                        && day_of_birth.is_ok(),
+                )
+                .detail("expected", "YYMMDD")
+                .detail("actual", &standard_id[4..10]),
+        ) {
+            return explanation;
         }
         let year_of_birth = get_year_of_birth(standard_id[4..6].chars().as_str());
-        let date_of_birth = NaiveDate::from_ymd_opt(
-            year_of_birth,
-            month_of_birth.unwrap(),
-            day_of_birth.unwrap(),
-        );
-        if date_of_birth.is_none() {
-            return false;
+        let month_of_birth = month_of_birth.unwrap();
+        let day_of_birth = day_of_birth.unwrap();
+        let date_of_birth = NaiveDate::from_ymd_opt(year_of_birth, month_of_birth, day_of_birth);
+        if !explanation.check(
+            explanation
+                .step("date of birth", date_of_birth.is_some())
+                .detail("year", year_of_birth)
+                .detail("month", month_of_birth)
+                .detail("day", day_of_birth),
+        ) {
+            return explanation;
         }
 
         let gender = standard_id[10..11].chars().nth(0).unwrap();
-        if gender != 'M' && gender != 'H' && gender != 'X' {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("gender", gender == 'M' || gender == 'H' || gender == 'X')
+                .detail("expected", "H, M or X")
+                .detail("actual", gender),
+        ) {
+            return explanation;
         }
 
-        if !standard_id[11..16].chars().all(|c| c.is_alphabetic()) {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "state and name consonants",
+                    standard_id[11..16].chars().all(|c| c.is_alphabetic()),
+                )
+                .detail("actual", &standard_id[11..16]),
+        ) {
+            return explanation;
         }
 
         let assigned_digit = standard_id[16..17].chars().nth(0).unwrap();
-        if (year_of_birth > 2000 && assigned_digit.is_digit(10))
-            || (year_of_birth <= 2000 && assigned_digit.is_alphabetic())
-        {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "century character",
+                    assigned_digit.is_ascii_alphanumeric()
+                        && !((year_of_birth > 2000 && assigned_digit.is_ascii_digit())
+                            || (year_of_birth <= 2000 && assigned_digit.is_alphabetic())),
+                )
+                .detail(
+                    "expected",
+                    if year_of_birth > 2000 {
+                        "letter"
+                    } else {
+                        "digit"
+                    },
+                )
+                .detail("actual", assigned_digit),
+        ) {
+            return explanation;
         }
 
-        if get_inappropriate_words_mexico()
-            .iter()
-            .find(|word| standard_id.contains(*word))
-            .is_some()
-        {
-            return false;
+        let inappropriate_word = get_inappropriate_words_mexico()
+            .into_iter()
+            .find(|word| standard_id.contains(word.as_str()));
+        if !explanation.check(
+            explanation
+                .step("inappropriate words", inappropriate_word.is_none())
+                .detail_with("found", || {
+                    inappropriate_word.unwrap_or_else(|| "none".to_string())
+                }),
+        ) {
+            return explanation;
         };
 
         const DICTIONARY: &str = "0123456789ABCDEFGHIJKLMN&OPQRSTUVWXYZ";
-        let mut sum = 0;
         let l = standard_id.len();
-        standard_id[0..17].chars().enumerate().for_each(|(i, c)| {
-            sum += DICTIONARY.find(c).unwrap() * (l - i);
-        });
+        let products: Vec<u32> = standard_id[0..17]
+            .chars()
+            .enumerate()
+            .map(|(i, c)| (DICTIONARY.find(c).unwrap() * (l - i)) as u32)
+            .collect();
+        let sum: u32 = products.iter().sum();
+        let expected_check_digit = (10 - (sum % 10)) % 10;
         let check_digit = standard_id[17..18].parse::<u32>();
 
-        !check_digit.is_err() && check_digit.unwrap() == ((10 - (sum % 10)) % 10) as u32
+        explanation.check(
+            explanation
+                .step("check digit", check_digit == Ok(expected_check_digit))
+                .detail_with("weights", || {
+                    format!("{:?}", (2..=18).rev().collect::<Vec<u32>>())
+                })
+                .detail_with("running sum", || running_sum(&products))
+                .detail("expected", expected_check_digit)
+                .detail("actual", &standard_id[17..18]),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         Code::MX
     }
 
+    fn encodes_name(&self) -> bool {
+        true
+    }
+
+    fn matches_name(&self, id: &str, name: &Name) -> bool {
+        let standard_id = self.sanitize_id(id);
+        let (initials, consonants) = get_name_letters(name);
+        standard_id[0..4] == initials && standard_id[13..16] == consonants
+    }
+
+    fn layout(&self) -> &'static str {
+        "LLLLDDDDDDLLLLLLAD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        layout::check_number(prefix, 6, 8, 1..=12, "month")?;
+        layout::check_number(prefix, 8, 10, 1..=31, "day")?;
+        if prefix.len() > 10 && !"HMX".contains(&prefix[10..11]) {
+            return Err(format!("invalid gender '{}'", &prefix[10..11]));
+        }
+        Ok(())
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separators: &[' ', '-'],
+            separator_positions: &[],
+        }
+    }
+
     fn extract_citizen(&self, id: &str) -> Option<Citizen> {
         if !self.validate_id(id) {
             return None;
         }
-        let standard_id = id.replace(" ", "").replace("-", "");
+        let standard_id = self.sanitize_id(id);
         Some(Citizen {
             gender: match standard_id[10..11].chars().nth(0).unwrap() {
                 'M' => 'F',
@@ -104,52 +213,143 @@ impl validator::CountryValidator for MexicoValidator {
             year_of_birth: get_year_of_birth(standard_id[4..6].chars().as_str()),
             month_of_birth: Some(standard_id[6..8].parse::<u32>().unwrap() as u8),
             day_of_birth: Some(standard_id[8..10].parse::<u32>().unwrap() as u8),
-            place_of_birth: extract_place_of_birth(standard_id[11..13].to_string()),
+            place_of_birth: extract_place_of_birth(&standard_id[11..13]),
         })
     }
 }
 
-fn extract_place_of_birth(code: String) -> Option<String> {
-    match code.as_str() {
-        "AS" => Some("AGUASCALIENTES".to_string()),
-        "BS" => Some("BAJA CALIFORNIA SUR".to_string()),
-        "CL" => Some("COAHUILA".to_string()),
-        "CS" => Some("CHIAPAS".to_string()),
-        "DF" => Some("DISTRITO FEDERAL".to_string()),
-        "GT" => Some("GUANAJUATO".to_string()),
-        "HG" => Some("HIDALGO".to_string()),
-        "MC" => Some("MÉXICO".to_string()),
-        "MS" => Some("MORELOS".to_string()),
-        "NL" => Some("NUEVO LEÓN".to_string()),
-        "PL" => Some("PUEBLA".to_string()),
-        "QR" => Some("QUINTANA ROO".to_string()),
-        "SL" => Some("SINALOA".to_string()),
-        "TC" => Some("TABASCO".to_string()),
-        "TL" => Some("TLAXCALA".to_string()),
-        "YN" => Some("YUCATÁN".to_string()),
-        "NE" => Some("NACIDO EN EL EXTRANJERO".to_string()),
-        "BC" => Some("BAJA CALIFORNIA".to_string()),
-        "CC" => Some("CAMPECHE".to_string()),
-        "CM" => Some("COLIMA".to_string()),
-        "CH" => Some("CHIHUAHUA".to_string()),
-        "DG" => Some("DURANGO".to_string()),
-        "GR" => Some("GUERRERO".to_string()),
-        "JC" => Some("JALISCO".to_string()),
-        "MN" => Some("MICHOACÁN".to_string()),
-        "NT" => Some("NAYARIT".to_string()),
-        "OC" => Some("OAXACA".to_string()),
-        "QT" => Some("QUERÉTARO".to_string()),
-        "SP" => Some("SAN LUIS POTOSÍ".to_string()),
-        "SR" => Some("SONORA".to_string()),
-        "TS" => Some("TAMAULIPAS".to_string()),
-        "VZ" => Some("VERACRUZ".to_string()),
-        "ZS" => Some("ZACATECAS".to_string()),
-        _ => None,
+const NAME_PARTICLES: [&str; 19] = [
+    "DA", "DAS", "DE", "DEL", "DER", "DI", "DIE", "DD", "EL", "LA", "LAS", "LE", "LES", "LOS",
+    "MAC", "MC", "VAN", "VON", "Y",
+];
+
+const COMMON_FIRST_NAMES: [&str; 6] = ["JOSE", "J", "MARIA", "MA", "M", "MX"];
+
+// Builds the four initials and the three inner consonants a CURP derives from a name.
+fn get_name_letters(name: &Name) -> (String, String) {
+    let first_surname = get_significant_word(&name.last_name);
+    let second_surname = name
+        .second_last_name
+        .as_ref()
+        .map(|surname| get_significant_word(surname))
+        .unwrap_or_default();
+    let mut given_names = get_words(&name.first_name);
+    if given_names.len() > 1 && COMMON_FIRST_NAMES.contains(&given_names[0].as_str()) {
+        given_names.remove(0);
     }
+    let given_name = given_names.into_iter().next().unwrap_or_default();
+
+    let mut initials: String = [
+        first_surname.chars().next().unwrap_or('X'),
+        first_surname
+            .chars()
+            .skip(1)
+            .find(|c| names::is_vowel(*c))
+            .unwrap_or('X'),
+        second_surname.chars().next().unwrap_or('X'),
+        given_name.chars().next().unwrap_or('X'),
+    ]
+    .iter()
+    .collect();
+    if get_inappropriate_words_mexico().contains(&initials) {
+        initials.replace_range(1..2, "X");
+    }
+
+    let consonants = [&first_surname, &second_surname, &given_name]
+        .iter()
+        .map(|word| {
+            word.chars()
+                .skip(1)
+                .find(|c| !names::is_vowel(*c))
+                .unwrap_or('X')
+        })
+        .collect();
+    (initials, consonants)
+}
+
+fn get_words(name: &str) -> Vec<String> {
+    // The RENAPO replaces Ñ with X instead of folding it into N.
+    names::normalize_name(&name.replace(['ñ', 'Ñ'], "X"))
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_string())
+        .collect()
+}
+
+fn get_significant_word(name: &str) -> String {
+    let words = get_words(name);
+    words
+        .iter()
+        .find(|word| !NAME_PARTICLES.contains(&word.as_str()))
+        .or_else(|| words.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn extract_place_of_birth(code: &str) -> Option<PlaceOfBirth> {
+    if code == "NE" {
+        return Some(
+            PlaceOfBirth::new(code, "NACIDO EN EL EXTRANJERO")
+                .foreign_born()
+                .translation("es", "NACIDO EN EL EXTRANJERO")
+                .translation("en", "BORN ABROAD"),
+        );
+    }
+
+    let (name, subdivision) = match code {
+        "AS" => ("AGUASCALIENTES", "AGU"),
+        "BS" => ("BAJA CALIFORNIA SUR", "BCS"),
+        "CL" => ("COAHUILA", "COA"),
+        "CS" => ("CHIAPAS", "CHP"),
+        "DF" => ("DISTRITO FEDERAL", "CMX"),
+        "GT" => ("GUANAJUATO", "GUA"),
+        "HG" => ("HIDALGO", "HID"),
+        "MC" => ("MÉXICO", "MEX"),
+        "MS" => ("MORELOS", "MOR"),
+        "NL" => ("NUEVO LEÓN", "NLE"),
+        "PL" => ("PUEBLA", "PUE"),
+        "QR" => ("QUINTANA ROO", "ROO"),
+        "SL" => ("SINALOA", "SIN"),
+        "TC" => ("TABASCO", "TAB"),
+        "TL" => ("TLAXCALA", "TLA"),
+        "YN" => ("YUCATÁN", "YUC"),
+        "BC" => ("BAJA CALIFORNIA", "BCN"),
+        "CC" => ("CAMPECHE", "CAM"),
+        "CM" => ("COLIMA", "COL"),
+        "CH" => ("CHIHUAHUA", "CHH"),
+        "DG" => ("DURANGO", "DUR"),
+        "GR" => ("GUERRERO", "GRO"),
+        "JC" => ("JALISCO", "JAL"),
+        "MN" => ("MICHOACÁN", "MIC"),
+        "NT" => ("NAYARIT", "NAY"),
+        "OC" => ("OAXACA", "OAX"),
+        "QT" => ("QUERÉTARO", "QUE"),
+        "SP" => ("SAN LUIS POTOSÍ", "SLP"),
+        "SR" => ("SONORA", "SON"),
+        "TS" => ("TAMAULIPAS", "TAM"),
+        "VZ" => ("VERACRUZ", "VER"),
+        "ZS" => ("ZACATECAS", "ZAC"),
+        _ => return None,
+    };
+    // most states keep their Spanish name in English
+    let english_name = match code {
+        "DF" => "MEXICO CITY",
+        "MC" => "STATE OF MEXICO",
+        _ => name,
+    };
+    Some(
+        PlaceOfBirth::new(code, name)
+            .subdivision(&format!("MX-{}", subdivision))
+            .country("MX")
+            .translation("es", name)
+            .translation("en", english_name),
+    )
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::consistency::Name;
+    use crate::validator::mexico::{extract_place_of_birth, get_name_letters};
     use crate::validator::CountryValidator;
 
     #[test]
@@ -222,6 +422,7 @@ mod tests {
         assert_eq!(true, validator.validate_id(" AAIM901112MBCNMN08 "));
         assert_eq!(true, validator.validate_id("JOIM890106HHGSMN08"));
         assert_eq!(true, validator.validate_id("JOTA950616HBCSWS03"));
+        assert_eq!(true, validator.validate_id("jota950616hbcsws03"));
     }
 
     #[test]
@@ -234,7 +435,7 @@ mod tests {
         assert_eq!(1990, c.year_of_birth);
         assert_eq!(11, c.month_of_birth.unwrap());
         assert_eq!(12, c.day_of_birth.unwrap());
-        assert_eq!("BAJA CALIFORNIA", c.place_of_birth.unwrap());
+        assert_eq!("BAJA CALIFORNIA", c.place_of_birth.unwrap().name);
 
         let citizen = validator.extract_citizen("AAJM900827MGTDPS05");
         assert_eq!(true, citizen.is_some());
@@ -243,7 +444,13 @@ mod tests {
         assert_eq!(1990, c.year_of_birth);
         assert_eq!(8, c.month_of_birth.unwrap());
         assert_eq!(27, c.day_of_birth.unwrap());
-        assert_eq!("GUANAJUATO", c.place_of_birth.unwrap());
+        let place_of_birth = c.place_of_birth.unwrap();
+        assert_eq!("GUANAJUATO", place_of_birth.name);
+        assert_eq!("GT", place_of_birth.code);
+        assert_eq!(Some("MX-GUA"), place_of_birth.subdivision.as_deref());
+        assert_eq!(Some("MX"), place_of_birth.country.as_deref());
+        assert_eq!("GUANAJUATO", place_of_birth.name_in("en"));
+        assert_eq!("GUANAJUATO", place_of_birth.name_in("es"));
 
         let citizen = validator.extract_citizen("JOIM890106HHGSMN08");
         assert_eq!(true, citizen.is_some());
@@ -252,6 +459,67 @@ mod tests {
         assert_eq!(1989, c.year_of_birth);
         assert_eq!(1, c.month_of_birth.unwrap());
         assert_eq!(6, c.day_of_birth.unwrap());
-        assert_eq!("HIDALGO", c.place_of_birth.unwrap());
+        assert_eq!("HIDALGO", c.place_of_birth.unwrap().name);
+    }
+
+    #[test]
+    fn mx_place_names() {
+        let place_of_birth = extract_place_of_birth("DF").unwrap();
+        assert_eq!("DISTRITO FEDERAL", place_of_birth.name_in("es"));
+        assert_eq!("MEXICO CITY", place_of_birth.name_in("en"));
+
+        let place_of_birth = extract_place_of_birth("NE").unwrap();
+        assert!(place_of_birth.foreign_born);
+        assert!(place_of_birth.country.is_none());
+        assert_eq!("BORN ABROAD", place_of_birth.name_in("en"));
+        assert!(extract_place_of_birth("XX").is_none());
+    }
+
+    #[test]
+    fn mx_explain() {
+        let validator = super::validator::mexico::MexicoValidator;
+        let explanation = validator.explain("JOIM890106HHGSMN08");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "check digit");
+
+        let explanation = validator.explain("AAIM901112VBCNMN08");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "gender");
+    }
+
+    #[test]
+    fn mx_name_letters() {
+        let name = Name {
+            first_name: "Gloria".to_string(),
+            last_name: "Hernández".to_string(),
+            second_last_name: Some("García".to_string()),
+        };
+        assert_eq!(
+            ("HEGG".to_string(), "RRL".to_string()),
+            get_name_letters(&name)
+        );
+
+        let name = Name {
+            first_name: "María Luisa".to_string(),
+            last_name: "de la Peña".to_string(),
+            second_last_name: None,
+        };
+        assert_eq!(
+            ("PEXL".to_string(), "XXS".to_string()),
+            get_name_letters(&name)
+        );
+    }
+
+    #[test]
+    fn mx_matches_name() {
+        let validator = super::validator::mexico::MexicoValidator;
+        let name = Name {
+            first_name: "Gloria".to_string(),
+            last_name: "Hernández".to_string(),
+            second_last_name: Some("García".to_string()),
+        };
+        assert!(validator.validate_id("HEGG560427MVZRRL04"));
+        assert!(validator.matches_name("HEGG560427MVZRRL04", &name));
+        assert!(!validator.matches_name("JOIM890106HHGSMN08", &name));
     }
 }
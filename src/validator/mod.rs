@@ -1,21 +1,64 @@
+use crate::consistency::Name;
 use crate::country;
+use crate::explanation::Explanation;
 use crate::Citizen;
 use chrono::NaiveDate;
+use sanitizer::SanitizeRules;
 
 mod algorithms;
 mod date;
+mod layout;
+pub(crate) mod names;
 mod regions;
+mod sanitizer;
 
 pub trait CountryValidator {
-    fn validate_id(&self, id: &str) -> bool;
+    /**
+     Runs the checks on an identifier. Details are only computed when `trace` is set.
+    **/
+    fn check_id(&self, id: &str, trace: bool) -> Explanation;
     fn country_code(&self) -> country::Code;
     fn extract_citizen(&self, id: &str) -> Option<Citizen>;
 
+    /**
+     Character classes of a complete sanitized identifier, see `layout::check_layout`.
+    **/
+    fn layout(&self) -> &'static str;
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())
+    }
+
+    fn encodes_name(&self) -> bool {
+        false
+    }
+
+    fn matches_name(&self, _id: &str, _name: &Name) -> bool {
+        false
+    }
+
+    fn validate_id(&self, id: &str) -> bool {
+        self.check_id(id, false).is_valid()
+    }
+
+    fn explain(&self, id: &str) -> Explanation {
+        self.check_id(id, true)
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        sanitizer::DEFAULT_RULES
+    }
+
     fn sanitize_id(&self, id: &str) -> String {
-        id.replace(" ", "")
-            .replace("-", "")
-            .replace(".", "")
-            .to_uppercase()
+        sanitizer::sanitize(id, &self.sanitize_rules())
+    }
+
+    fn sanitize_id_strict(&self, id: &str) -> Option<String> {
+        sanitizer::sanitize_strict(id, &self.sanitize_rules())
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        self.sanitize_id(id)
     }
 
     fn is_date_valid(&self, year: u32, month: u32, day: u32) -> bool {
@@ -24,20 +67,20 @@ pub trait CountryValidator {
 }
 
 mod albania;
-mod belgium;
+pub(crate) mod belgium;
 mod bosniaherzegovina;
-mod brazil;
+pub(crate) mod brazil;
 mod bulgaria;
-mod canada;
-mod denmark;
-mod france;
-mod germany;
-mod italy;
+pub(crate) mod canada;
+pub(crate) mod denmark;
+pub(crate) mod france;
+pub(crate) mod germany;
+pub(crate) mod italy;
 mod luxembourg;
 mod mexico;
-mod portugal;
-mod spain;
-mod usa;
+pub(crate) mod portugal;
+pub(crate) mod spain;
+pub(crate) mod usa;
 mod words;
 
 pub fn get_validator(country: &country::Code) -> Box<dyn CountryValidator> {
@@ -63,6 +106,7 @@ pub fn get_validator(country: &country::Code) -> Box<dyn CountryValidator> {
 #[cfg(test)]
 mod tests {
     use crate::country::Code;
+    use crate::explanation::Explanation;
     use crate::validator::CountryValidator;
     use crate::Citizen;
     use std::mem;
@@ -71,7 +115,7 @@ mod tests {
     struct TestValidator {}
 
     impl CountryValidator for TestValidator {
-        fn validate_id(&self, _id: &str) -> bool {
+        fn check_id(&self, _id: &str, _trace: bool) -> Explanation {
             panic!()
         }
 
@@ -82,6 +126,10 @@ mod tests {
         fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
             panic!()
         }
+
+        fn layout(&self) -> &'static str {
+            "LDD"
+        }
     }
 
     #[test]
@@ -91,6 +139,12 @@ mod tests {
         assert_eq!(validator.sanitize_id("1-"), "1");
         assert_eq!(validator.sanitize_id("1 "), "1");
         assert_eq!(validator.sanitize_id(" 1 - 2"), "12");
+        assert_eq!(validator.sanitize_id("1\u{2013}2"), "12");
+        assert_eq!(validator.sanitize_id_strict("1"), Some("1".to_string()));
+        assert_eq!(validator.sanitize_id_strict("1-2"), None);
+
+        assert!(validator.validate_prefix("A1").is_ok());
+        assert!(validator.validate_prefix("11").is_err());
 
         assert!(validator.is_date_valid(2021, 1, 10));
         assert!(!validator.is_date_valid(2021, 15, 1));
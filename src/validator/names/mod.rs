@@ -0,0 +1,53 @@
+/**
+ Upper cases a person name and replaces accented letters with their base letter,
+ keeping only letters and spaces, as done by the registries deriving codes from names.
+**/
+pub fn normalize_name(name: &str) -> String {
+    name.to_uppercase()
+        .chars()
+        .map(strip_accent)
+        .filter(|c| c.is_alphabetic() || *c == ' ')
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn strip_accent(c: char) -> char {
+    match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => 'A',
+        'È' | 'É' | 'Ê' | 'Ë' => 'E',
+        'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => 'O',
+        'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+        'Ç' => 'C',
+        'Ñ' => 'N',
+        'Ý' => 'Y',
+        '\'' | '’' | '-' => ' ',
+        c => c,
+    }
+}
+
+pub fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validator::names::{is_vowel, normalize_name};
+
+    #[test]
+    fn normalizes_names() {
+        assert_eq!("HERNANDEZ GARCIA", normalize_name(" Hernández  García "));
+        assert_eq!("D ANGELO", normalize_name("D'Angelo"));
+        assert_eq!("NICOLO", normalize_name("Nicolò"));
+        assert_eq!("PENA", normalize_name("Peña"));
+        assert_eq!("NUNEZ", normalize_name("NÚÑEZ"));
+    }
+
+    #[test]
+    fn vowels() {
+        assert!(is_vowel('A'));
+        assert!(!is_vowel('B'));
+    }
+}
@@ -1,30 +1,172 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
 use crate::{validator, Citizen};
 
 pub(crate) struct PortugalValidator;
 
+pub(crate) struct NifValidator;
+
+pub(crate) struct NissValidator;
+
+const NIF_WEIGHTS: [u32; 8] = [9, 8, 7, 6, 5, 4, 3, 2];
+const NISS_WEIGHTS: [u32; 10] = [29, 23, 19, 17, 13, 11, 7, 5, 3, 2];
+
+/**
+ Kind of taxpayer a Portuguese NIF was given to, by its first digits.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NifEntity {
+    Individual,
+    NonResidentIndividual,
+    Company,
+    PublicBody,
+    UndividedEstate,
+    NonResidentCompany,
+    InvestmentFund,
+    OfficialAssignment,
+    ExceptionalRegime,
+    SoleTrader,
+    Condominium,
+    NonResident,
+    CivilPartnership,
+}
+
+/**
+ Returns the civil identification number of the holder of a valid Citizen Card, which is kept
+ across reissues and was also the number of the former identity card (Bilhete de Identidade).
+**/
+pub fn civil_id_number(id: &str) -> Option<String> {
+    if !PortugalValidator.validate_id(id) {
+        return None;
+    }
+
+    Some(PortugalValidator.sanitize_id(id)[0..8].to_string())
+}
+
+/**
+ Returns how many times a valid Citizen Card was reissued, from its two version characters.
+ The first card has version ZZ and each reissue counts down in base 36: ZY, ZX, ..., ZA, Z9 and so on.
+**/
+pub fn reissue_count(id: &str) -> Option<u32> {
+    if !PortugalValidator.validate_id(id) {
+        return None;
+    }
+
+    let version = u32::from_str_radix(&PortugalValidator.sanitize_id(id)[9..11], 36).ok()?;
+    Some(36 * 36 - 1 - version)
+}
+
+/**
+ Validates a NIF, the Portuguese tax number.
+**/
+pub fn validate_nif(id: &str) -> bool {
+    NifValidator.validate_id(id)
+}
+
+/**
+ Traces the checks performed on a NIF, see `explain`.
+**/
+pub fn explain_nif(id: &str) -> Explanation {
+    NifValidator.explain(id)
+}
+
+/**
+ Returns the kind of taxpayer a valid NIF was given to.
+**/
+pub fn nif_entity(id: &str) -> Option<NifEntity> {
+    if !NifValidator.validate_id(id) {
+        return None;
+    }
+
+    get_nif_entity(&NifValidator.sanitize_id(id))
+}
+
+/**
+ Validates a NISS, the Portuguese social security number.
+**/
+pub fn validate_niss(id: &str) -> bool {
+    NissValidator.validate_id(id)
+}
+
+/**
+ Traces the checks performed on a NISS, see `explain`.
+**/
+pub fn explain_niss(id: &str) -> Explanation {
+    NissValidator.explain(id)
+}
+
+fn get_nif_entity(standard_id: &str) -> Option<NifEntity> {
+    match &standard_id[0..2] {
+        "45" => Some(NifEntity::NonResidentIndividual),
+        "70" | "74" | "75" => Some(NifEntity::UndividedEstate),
+        "71" => Some(NifEntity::NonResidentCompany),
+        "72" => Some(NifEntity::InvestmentFund),
+        "77" | "78" => Some(NifEntity::OfficialAssignment),
+        "79" => Some(NifEntity::ExceptionalRegime),
+        "90" | "91" => Some(NifEntity::Condominium),
+        "98" => Some(NifEntity::NonResident),
+        "99" => Some(NifEntity::CivilPartnership),
+        prefix => match &prefix[0..1] {
+            "1" | "2" | "3" => Some(NifEntity::Individual),
+            "5" => Some(NifEntity::Company),
+            "6" => Some(NifEntity::PublicBody),
+            "8" => Some(NifEntity::SoleTrader),
+            _ => None,
+        },
+    }
+}
+
+fn weighted_products(standard_id: &str, weights: &[u32]) -> Vec<u32> {
+    standard_id
+        .chars()
+        .zip(weights.iter())
+        .map(|(digit, weight)| digit.to_digit(10).unwrap() * weight)
+        .collect()
+}
+
 /**
  Portugal national citizen card number validation.
 
  This validation algorithm is based on the official documentation released in 26 of January of 2009.
  Link: https://www.autenticacao.gov.pt/documents/20126/115760/Valida%C3%A7%C3%A3o+de+N%C3%BAmero+de+Documento+do+Cart%C3%A3o+de+Cidad%C3%A3o.pdf/bdc4eb37-7316-3ff4-164a-f869382b7053
+
+ The document number is made of the civil identification number, its check digit, the version of
+ the card and a check digit. It encodes neither the birth date nor the gender of its holder, so no
+ citizen can be extracted: see `portugal::civil_id_number` and `portugal::reissue_count` instead.
 **/
 impl validator::CountryValidator for PortugalValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
-        if standard_id.len() != 12 {
-            return false;
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step("length", standard_id.len() == 12)
+                .detail("expected", 12)
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let format = layout::check_layout(&standard_id, self.layout());
+        if !explanation.check(
+            explanation
+                .step("format", format.is_ok())
+                .detail_with("layout", || self.layout())
+                .detail_with("error", || {
+                    format.err().unwrap_or_else(|| "none".to_string())
+                }),
+        ) {
+            return explanation;
         }
 
         let mut every_other_digit = false;
-        let mut sum: u32 = 0;
+        let mut values: Vec<u32> = vec![];
         for char in standard_id.chars().rev() {
-            let potential_char_value = char.to_digit(36);
-            if potential_char_value.is_none() {
-                return false;
-            }
-
-            let mut char_value = potential_char_value.unwrap();
+            let mut char_value = char.to_digit(36).unwrap();
             if every_other_digit {
                 char_value *= 2;
                 if char_value > 9 {
@@ -32,22 +174,165 @@ impl validator::CountryValidator for PortugalValidator {
                 }
             }
 
-            sum += char_value;
+            values.push(char_value);
             every_other_digit = !every_other_digit;
         }
 
-        return (sum % 10) == 0;
+        let sum: u32 = values.iter().sum();
+        explanation.check(
+            explanation
+                .step("checksum", (sum % 10) == 0)
+                .detail_with("values from the right", || format!("{:?}", values))
+                .detail_with("running sum", || running_sum(&values))
+                .detail("expected", "sum divisible by 10")
+                .detail("actual", sum),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::PT;
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDAAD"
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[8, 9],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{} {} {}",
+            &standard_id[0..8],
+            &standard_id[8..9],
+            &standard_id[9..12]
+        )
+    }
+
     fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
         return None;
     }
 }
 
+/**
+ Portugal tax number (NIF) validation: 9 digits, the last one a modulus 11 check digit.
+ The first digits give the kind of taxpayer, see `NifEntity`.
+ Link: https://pt.wikipedia.org/wiki/N%C3%BAmero_de_identifica%C3%A7%C3%A3o_fiscal
+**/
+impl validator::CountryValidator for NifValidator {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
+        let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 9 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "9 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let entity = get_nif_entity(&standard_id);
+        if !explanation.check(
+            explanation
+                .step("entity type", entity.is_some())
+                .detail("prefix", &standard_id[0..2])
+                .detail_with("entity", || format!("{:?}", entity)),
+        ) {
+            return explanation;
+        }
+
+        let products = weighted_products(&standard_id, &NIF_WEIGHTS);
+        let remainder = products.iter().sum::<u32>() % 11;
+        let expected = if remainder < 2 { 0 } else { 11 - remainder };
+        explanation.check(
+            explanation
+                .step("check digit", standard_id[8..9] == expected.to_string())
+                .detail_with("weights", || format!("{:?}", NIF_WEIGHTS))
+                .detail_with("running sum", || running_sum(&products))
+                .detail("expected", expected)
+                .detail("actual", &standard_id[8..9]),
+        );
+        explanation
+    }
+
+    fn country_code(&self) -> Code {
+        Code::PT
+    }
+
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDD"
+    }
+
+    fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
+        None
+    }
+}
+
+/**
+ Portugal social security number (NISS) validation: 11 digits starting with 1 for people or 2 for
+ companies, the last one a check digit computed with the prime weights 29 to 2.
+**/
+impl validator::CountryValidator for NissValidator {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
+        let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 11 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "11 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        if !explanation.check(
+            explanation
+                .step("prefix", matches!(&standard_id[0..1], "1" | "2"))
+                .detail("expected", "1 or 2")
+                .detail("actual", &standard_id[0..1]),
+        ) {
+            return explanation;
+        }
+
+        let products = weighted_products(&standard_id, &NISS_WEIGHTS);
+        let expected = 9 - products.iter().sum::<u32>() % 10;
+        explanation.check(
+            explanation
+                .step("check digit", standard_id[10..11] == expected.to_string())
+                .detail_with("weights", || format!("{:?}", NISS_WEIGHTS))
+                .detail_with("running sum", || running_sum(&products))
+                .detail("expected", expected)
+                .detail("actual", &standard_id[10..11]),
+        );
+        explanation
+    }
+
+    fn country_code(&self) -> Code {
+        Code::PT
+    }
+
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDDDD"
+    }
+
+    fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::validator::CountryValidator;
@@ -80,4 +365,73 @@ mod tests {
         let validator = super::validator::portugal::PortugalValidator;
         assert_eq!(validator.extract_citizen("11084129 8 ZX8").is_none(), true);
     }
+
+    #[test]
+    fn pt_explain() {
+        let validator = super::validator::portugal::PortugalValidator;
+        let explanation = validator.explain("11084129 8 ZX8");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "checksum");
+
+        let explanation = validator.explain("14897475 4 ZY5");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "checksum");
+    }
+
+    #[test]
+    fn pt_nif() {
+        use crate::validator::portugal::{explain_nif, nif_entity, validate_nif, NifEntity};
+
+        assert!(validate_nif("123456789"));
+        assert!(validate_nif("500 697 256"));
+        assert!(validate_nif("451234561"));
+        assert!(!validate_nif("123456788"));
+        assert!(!validate_nif("12345678"));
+        assert!(!validate_nif("12345678A"));
+        assert!(!validate_nif("000000000"));
+
+        assert_eq!(nif_entity("123456789"), Some(NifEntity::Individual));
+        assert_eq!(nif_entity("500697256"), Some(NifEntity::Company));
+        assert_eq!(
+            nif_entity("451234561"),
+            Some(NifEntity::NonResidentIndividual)
+        );
+        assert_eq!(nif_entity("600000001"), Some(NifEntity::PublicBody));
+        assert_eq!(nif_entity("710000006"), Some(NifEntity::NonResidentCompany));
+        assert_eq!(nif_entity("981234569"), Some(NifEntity::NonResident));
+        assert_eq!(nif_entity("123456788"), None);
+
+        let explanation = explain_nif("000000000");
+        assert_eq!(explanation.failed_step().unwrap().check, "entity type");
+    }
+
+    #[test]
+    fn pt_niss() {
+        use crate::validator::portugal::{explain_niss, validate_niss};
+
+        assert!(validate_niss("11234567892"));
+        assert!(validate_niss("20000000019"));
+        assert!(!validate_niss("11234567893"));
+        assert!(!validate_niss("1123456789"));
+        assert!(!validate_niss("31234567892"));
+
+        let explanation = explain_niss("11234567893");
+        assert_eq!(explanation.failed_step().unwrap().check, "check digit");
+    }
+
+    #[test]
+    fn pt_citizen_card_issue() {
+        use crate::validator::portugal::{civil_id_number, reissue_count};
+
+        assert_eq!(reissue_count("17653917 4ZZ5"), Some(0));
+        assert_eq!(reissue_count("14898475 4 ZY5"), Some(1));
+        assert_eq!(reissue_count("11084129 8 ZX8"), Some(2));
+        assert_eq!(reissue_count("14897475 4 ZY5"), None);
+
+        assert_eq!(
+            civil_id_number("11084129 8 ZX8"),
+            Some("11084129".to_string())
+        );
+        assert_eq!(civil_id_number("14897475 4 ZY5"), None);
+    }
 }
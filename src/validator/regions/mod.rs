@@ -1,35 +1,131 @@
-use std::fs::File;
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
+use chrono::NaiveDate;
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
-struct Region {
-    code: String,
-    region: String,
+/**
+ Italian comuni and foreign states by Belfiore code, including the comuni suppressed up to 2016.
+**/
+pub static ITALY_REGIONS: RegionTable = RegionTable::new(include_str!("italy_regions.csv"));
+
+/**
+ Countries by the code INSEE gives them in the Code Officiel Géographique (99 followed by the
+ continent and the country), with their ISO 3166-1 code. Only the countries existing today are listed.
+**/
+pub static FRANCE_COUNTRIES: RegionTable = RegionTable::new(include_str!("france_countries.csv"));
+
+/**
+ A place listed in a region table, together with the period in which its code was in use.
+ Open ended intervals are left empty in the table.
+**/
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct HistoricalRegion {
+    pub code: String,
+    pub region: String,
+    pub province: Option<String>,
+    pub valid_from: Option<String>,
+    pub valid_to: Option<String>,
+    pub iso_code: Option<String>,
+    pub name_en: Option<String>,
+}
+
+impl HistoricalRegion {
+    pub fn is_valid_at(&self, date: NaiveDate) -> bool {
+        let after_start = self
+            .valid_from
+            .as_deref()
+            .is_none_or(|from| parse_date(from) <= date);
+        let before_end = self
+            .valid_to
+            .as_deref()
+            .is_none_or(|to| date <= parse_date(to));
+        after_start && before_end
+    }
 }
 
-pub fn get_region_from_csv(code: &str, file_path: &str) -> Option<String> {
-    let file = File::open(file_path).unwrap();
-    let mut rdr = csv::Reader::from_reader(file);
-    for result in rdr.deserialize() {
-        let record: Region = result.unwrap();
-        if record.code == code.to_owned() {
-            return Some(record.region);
+/**
+ A region table compiled into the crate, parsed and indexed by code the first time it is read.
+**/
+pub struct RegionTable {
+    source: &'static str,
+    regions: OnceLock<HashMap<String, Vec<HistoricalRegion>>>,
+}
+
+impl RegionTable {
+    const fn new(source: &'static str) -> RegionTable {
+        RegionTable {
+            source,
+            regions: OnceLock::new(),
         }
     }
 
-    return None;
+    fn regions(&self) -> &HashMap<String, Vec<HistoricalRegion>> {
+        self.regions.get_or_init(|| {
+            let mut regions: HashMap<String, Vec<HistoricalRegion>> = HashMap::new();
+            let mut rdr = csv::Reader::from_reader(self.source.as_bytes());
+            for record in rdr.deserialize() {
+                let record: HistoricalRegion = record.unwrap();
+                regions.entry(record.code.clone()).or_default().push(record);
+            }
+            regions
+        })
+    }
+}
+
+pub fn get_historical_regions(code: &str, table: &RegionTable) -> Vec<HistoricalRegion> {
+    table.regions().get(code).cloned().unwrap_or_default()
+}
+
+/**
+ Returns the region a code stood for at the given date, if the code was in use then.
+**/
+pub fn get_region_at_date(
+    code: &str,
+    date: NaiveDate,
+    table: &RegionTable,
+) -> Option<HistoricalRegion> {
+    get_historical_regions(code, table)
+        .into_iter()
+        .find(|region| region.is_valid_at(date))
+}
+
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::validator::regions::get_region_from_csv;
+    use crate::validator::regions::{get_historical_regions, get_region_at_date, ITALY_REGIONS};
+    use chrono::NaiveDate;
 
     #[test]
     fn year_of_birth() {
         assert_eq!(
             "ALBANIA",
-            get_region_from_csv("Z100", "./src/validator/regions/italy_regions.csv").unwrap()
+            get_historical_regions("Z100", &ITALY_REGIONS)[0].region
+        );
+    }
+
+    #[test]
+    fn historical_regions() {
+        let milano = get_historical_regions("F205", &ITALY_REGIONS);
+        assert_eq!(1, milano.len());
+        assert!(milano[0].valid_from.is_none());
+
+        let ussr = get_historical_regions("Z135", &ITALY_REGIONS);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert!(ussr[0].is_valid_at(date(1991, 12, 25)));
+        assert!(!ussr[0].is_valid_at(date(1991, 12, 26)));
+
+        assert_eq!(
+            "CECA REPUBBLICA",
+            get_region_at_date("Z156", date(1993, 1, 1), &ITALY_REGIONS)
+                .unwrap()
+                .region
         );
+        assert!(get_region_at_date("Z156", date(1992, 12, 31), &ITALY_REGIONS).is_none());
+        assert!(get_region_at_date("Z999", date(1992, 12, 31), &ITALY_REGIONS).is_none());
     }
 }
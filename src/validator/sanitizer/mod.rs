@@ -0,0 +1,104 @@
+/**
+ Describes how an identifier may be written by a person before it is validated.
+
+ `separators` are the characters that are stripped from the identifier and
+ `separator_positions` lists, for the strict mode, after how many significant characters
+ a separator is allowed to appear.
+**/
+pub struct SanitizeRules {
+    pub separators: &'static [char],
+    pub separator_positions: &'static [usize],
+}
+
+pub const DEFAULT_RULES: SanitizeRules = SanitizeRules {
+    separators: &[' ', '-', '.'],
+    separator_positions: &[],
+};
+
+pub fn sanitize(id: &str, rules: &SanitizeRules) -> String {
+    id.chars()
+        .map(normalize_char)
+        .filter(|c| !rules.separators.contains(c))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+pub fn sanitize_strict(id: &str, rules: &SanitizeRules) -> Option<String> {
+    let normalized: String = id.chars().map(normalize_char).collect();
+    let mut standard_id = String::new();
+    let mut last_separator_position = None;
+    for c in normalized.trim().chars() {
+        if !rules.separators.contains(&c) {
+            standard_id.push(c);
+            continue;
+        }
+
+        let position = standard_id.chars().count();
+        if !rules.separator_positions.contains(&position)
+            || last_separator_position == Some(position)
+        {
+            return None;
+        }
+        last_separator_position = Some(position);
+    }
+
+    Some(standard_id.to_uppercase())
+}
+
+// Maps the unicode look-alikes found in copy-pasted and typed identifiers to ASCII.
+fn normalize_char(c: char) -> char {
+    match c {
+        '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE63}' => '-',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap(),
+        c if c.is_whitespace() => ' ',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validator::sanitizer::{sanitize, sanitize_strict, SanitizeRules, DEFAULT_RULES};
+
+    const SLASH_RULES: SanitizeRules = SanitizeRules {
+        separators: &[' ', '-', '/'],
+        separator_positions: &[6],
+    };
+
+    #[test]
+    fn sanitize_removes_separators() {
+        assert_eq!("12", sanitize(" 1 - 2", &DEFAULT_RULES));
+        assert_eq!("1/2", sanitize("1/2", &DEFAULT_RULES));
+        assert_eq!("12", sanitize("1/2", &SLASH_RULES));
+        assert_eq!("ABC", sanitize("abc", &DEFAULT_RULES));
+    }
+
+    #[test]
+    fn sanitize_normalizes_unicode() {
+        assert_eq!("0907921395", sanitize("090792\u{2013}1395", &DEFAULT_RULES));
+        assert_eq!("0907921395", sanitize("090792\u{00A0}1395", &DEFAULT_RULES));
+        assert_eq!(
+            "0907921395",
+            sanitize("０９０７９２－１３９５", &DEFAULT_RULES)
+        );
+        assert_eq!("AB", sanitize("ａｂ", &DEFAULT_RULES));
+    }
+
+    #[test]
+    fn sanitize_strict_checks_separator_positions() {
+        assert_eq!(
+            Some("0907921395".to_string()),
+            sanitize_strict(" 090792-1395 ", &SLASH_RULES)
+        );
+        assert_eq!(
+            Some("0907921395".to_string()),
+            sanitize_strict("090792/1395", &SLASH_RULES)
+        );
+        assert_eq!(
+            Some("0907921395".to_string()),
+            sanitize_strict("0907921395", &SLASH_RULES)
+        );
+        assert_eq!(None, sanitize_strict("09079-21395", &SLASH_RULES));
+        assert_eq!(None, sanitize_strict("090792 - 1395", &SLASH_RULES));
+        assert_eq!(None, sanitize_strict("0907-921395", &DEFAULT_RULES));
+    }
+}
@@ -1,46 +1,316 @@
 use crate::country::Code;
+use crate::explanation::{running_sum, Explanation};
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
 use crate::{validator, Citizen};
 
 pub(crate) struct SpainValidator;
 
+pub(crate) struct CifValidator;
+
 const CONTROL_DIGIT: &str = "TRWAGMYFPDXBNJZSQVHLCKE";
+const CIF_CONTROL_LETTER: &str = "JABCDEFGHI";
+const CIF_ENTITY_LETTERS: &str = "ABCDEFGHJNPQRSUVW";
+
+/**
+ Spanish identification documents.
+
+ `Dni` is the number of Spanish citizens and `Nie` the number of foreign residents, starting with
+ X, Y or Z. `SpecialNif` is the tax number of people without either: Spaniards under 14 (K),
+ Spaniards living abroad (L) and foreigners without a NIE (M). `Cif` is the tax number of companies
+ and other legal entities.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentType {
+    Dni,
+    Nie,
+    SpecialNif,
+    Cif,
+}
+
+/**
+ Kind of legal entity, given by the first letter of a CIF.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CifEntity {
+    PublicLimitedCompany,
+    LimitedCompany,
+    GeneralPartnership,
+    LimitedPartnership,
+    CommunityOfProperty,
+    Cooperative,
+    Association,
+    HomeownersAssociation,
+    CivilPartnership,
+    ForeignEntity,
+    LocalAuthority,
+    PublicBody,
+    ReligiousInstitution,
+    StateAdministration,
+    TemporaryJointVenture,
+    Other,
+    PermanentEstablishment,
+}
+
+/**
+ Returns the type of a valid Spanish identification number, personal or CIF.
+**/
+pub fn document_type(id: &str) -> Option<DocumentType> {
+    if SpainValidator.validate_id(id) {
+        get_document_type(&SpainValidator.sanitize_id(id))
+    } else if CifValidator.validate_id(id) {
+        Some(DocumentType::Cif)
+    } else {
+        None
+    }
+}
+
+/**
+ Validates a CIF, the tax number of Spanish companies and other legal entities.
+**/
+pub fn validate_cif(id: &str) -> bool {
+    CifValidator.validate_id(id)
+}
+
+/**
+ Traces the checks performed on a CIF, see `explain`.
+**/
+pub fn explain_cif(id: &str) -> Explanation {
+    CifValidator.explain(id)
+}
+
+/**
+ Returns the kind of entity a valid CIF belongs to.
+**/
+pub fn cif_entity(id: &str) -> Option<CifEntity> {
+    if !CifValidator.validate_id(id) {
+        return None;
+    }
+
+    get_cif_entity(CifValidator.sanitize_id(id).chars().next()?)
+}
+
+fn get_document_type(standard_id: &str) -> Option<DocumentType> {
+    match standard_id.chars().next()? {
+        '0'..='9' => Some(DocumentType::Dni),
+        'X' | 'Y' | 'Z' => Some(DocumentType::Nie),
+        'K' | 'L' | 'M' => Some(DocumentType::SpecialNif),
+        _ => None,
+    }
+}
+
+fn get_cif_entity(letter: char) -> Option<CifEntity> {
+    match letter {
+        'A' => Some(CifEntity::PublicLimitedCompany),
+        'B' => Some(CifEntity::LimitedCompany),
+        'C' => Some(CifEntity::GeneralPartnership),
+        'D' => Some(CifEntity::LimitedPartnership),
+        'E' => Some(CifEntity::CommunityOfProperty),
+        'F' => Some(CifEntity::Cooperative),
+        'G' => Some(CifEntity::Association),
+        'H' => Some(CifEntity::HomeownersAssociation),
+        'J' => Some(CifEntity::CivilPartnership),
+        'N' => Some(CifEntity::ForeignEntity),
+        'P' => Some(CifEntity::LocalAuthority),
+        'Q' => Some(CifEntity::PublicBody),
+        'R' => Some(CifEntity::ReligiousInstitution),
+        'S' => Some(CifEntity::StateAdministration),
+        'U' => Some(CifEntity::TemporaryJointVenture),
+        'V' => Some(CifEntity::Other),
+        'W' => Some(CifEntity::PermanentEstablishment),
+        _ => None,
+    }
+}
 
 /**
  Spain national citizen card number validation.
 
  This validation algorithm is based on the official documentation.
  Link: http://www.interior.gob.es/web/servicios-al-ciudadano/dni/calculo-del-digito-de-control-del-nif-nie
+
+ Besides the DNI and the NIE, the NIFs starting with K, L or M are accepted: their control letter
+ is computed on the 7 digits following the letter.
 **/
 impl validator::CountryValidator for SpainValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
 
-        if standard_id.len() != 9 {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("length", standard_id.len() == 9 && standard_id.is_ascii())
+                .detail("expected", 9)
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
-        let control = &standard_id[standard_id.len() - 1..standard_id.len()];
-        let citizen = &standard_id[0..standard_id.len() - 1]
-            .replace("X", "0")
-            .replace("Y", "1")
-            .replace("Z", "2")
-            .parse::<usize>()
-            .unwrap();
+        let document_type = get_document_type(&standard_id);
+        let control = &standard_id[8..9];
+        let number = match document_type {
+            Some(DocumentType::Nie) => {
+                let prefix = match &standard_id[0..1] {
+                    "X" => "0",
+                    "Y" => "1",
+                    _ => "2",
+                };
+                format!("{}{}", prefix, &standard_id[1..8])
+            }
+            Some(DocumentType::SpecialNif) => standard_id[1..8].to_string(),
+            _ => standard_id[0..8].to_string(),
+        };
+        let citizen = if number.chars().all(|c| c.is_ascii_digit()) {
+            number.parse::<usize>().ok()
+        } else {
+            None
+        };
+        if !explanation.check(
+            explanation
+                .step("format", document_type.is_some() && citizen.is_some())
+                .detail("expected", "8 digits, or X/Y/Z/K/L/M followed by 7 digits")
+                .detail_with("document type", || format!("{:?}", document_type))
+                .detail("number", &number),
+        ) {
+            return explanation;
+        }
 
-        let result = citizen % 23;
+        let result = citizen.unwrap() % 23;
         let validation = &CONTROL_DIGIT[result..result + 1];
-        return validation == control;
+        explanation.check(
+            explanation
+                .step("control letter", validation == control)
+                .detail("number mod 23", result)
+                .detail("expected", validation)
+                .detail("actual", control),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::ES;
     }
 
+    fn layout(&self) -> &'static str {
+        "ADDDDDDDL"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        if let Some(first) = prefix.chars().next() {
+            if !first.is_ascii_digit() && !"XYZKLM".contains(first) {
+                return Err(format!("invalid first character '{}'", first));
+            }
+        }
+        Ok(())
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[1, 2, 5, 8],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
     fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
         return None;
     }
 }
 
+/**
+ Spain company tax number (CIF) validation.
+
+ A letter giving the kind of entity is followed by 7 digits and a control character. The control
+ is a letter for entities such as public bodies and foreign entities, a digit for companies and
+ either of them for the others.
+ Link: https://es.wikipedia.org/wiki/C%C3%B3digo_de_identificaci%C3%B3n_fiscal
+**/
+impl validator::CountryValidator for CifValidator {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
+        let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
+
+        if !explanation.check(
+            explanation
+                .step("length", standard_id.len() == 9 && standard_id.is_ascii())
+                .detail("expected", 9)
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
+        }
+
+        let entity = standard_id.chars().next().and_then(get_cif_entity);
+        if !explanation.check(
+            explanation
+                .step(
+                    "format",
+                    entity.is_some() && layout::check_layout(&standard_id, self.layout()).is_ok(),
+                )
+                .detail_with("expected", || {
+                    format!(
+                        "one of {} followed by 7 digits and a control character",
+                        CIF_ENTITY_LETTERS
+                    )
+                })
+                .detail_with("entity", || format!("{:?}", entity)),
+        ) {
+            return explanation;
+        }
+
+        let values: Vec<u32> = standard_id[1..8]
+            .chars()
+            .enumerate()
+            .map(|(idx, c)| {
+                let digit = c.to_digit(10).unwrap();
+                if idx % 2 == 0 {
+                    digit * 2 / 10 + digit * 2 % 10
+                } else {
+                    digit
+                }
+            })
+            .collect();
+        let control = (10 - values.iter().sum::<u32>() % 10) % 10;
+        let letter = &CIF_CONTROL_LETTER[control as usize..control as usize + 1];
+        let expected = match &standard_id[0..1] {
+            "N" | "P" | "Q" | "R" | "S" | "W" => letter.to_string(),
+            "A" | "B" | "E" | "H" => control.to_string(),
+            _ => format!("{} or {}", control, letter),
+        };
+        let actual = &standard_id[8..9];
+        explanation.check(
+            explanation
+                .step(
+                    "control character",
+                    expected.split(" or ").any(|option| option == actual),
+                )
+                .detail_with("running sum", || running_sum(&values))
+                .detail("expected", expected)
+                .detail("actual", actual),
+        );
+        explanation
+    }
+
+    fn country_code(&self) -> Code {
+        Code::ES
+    }
+
+    fn layout(&self) -> &'static str {
+        "LDDDDDDDA"
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[1, 8],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::validator::CountryValidator;
@@ -56,6 +326,14 @@ mod tests {
     fn es_validator_invalid_ids() {
         let validator = super::validator::spain::SpainValidator;
         assert_eq!(validator.validate_id("05756786M"), false);
+        assert_eq!(validator.validate_id("A58818501"), false);
+        assert_eq!(validator.validate_id("+1234567L"), false);
+        assert_eq!(validator.validate_id("X+234567L"), false);
+        assert_eq!(validator.validate_id("K1234567T"), false);
+        assert_eq!(validator.validate_id("XX234567J"), false);
+        assert_eq!(validator.validate_id("XY234567L"), false);
+        assert_eq!(validator.validate_id("ZY234567R"), false);
+        assert_eq!(validator.validate_id("X0234Z67J"), false);
     }
 
     #[test]
@@ -66,5 +344,66 @@ mod tests {
         assert_eq!(validator.validate_id("40298386V"), true);
         assert_eq!(validator.validate_id("Y0597591L"), true);
         assert_eq!(validator.validate_id("09730915Y"), true);
+        assert_eq!(validator.validate_id("K1234567L"), true);
+        assert_eq!(validator.validate_id("L5070003K"), true);
+        assert_eq!(validator.validate_id("M0867756N"), true);
+        assert_eq!(validator.validate_id("X0234567J"), true);
+    }
+
+    #[test]
+    fn es_explain() {
+        let validator = super::validator::spain::SpainValidator;
+        let explanation = validator.explain("Y3338121F");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "control letter");
+
+        let explanation = validator.explain("05756786M");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "control letter");
+
+        let explanation = validator.explain("A58818501");
+        assert_eq!(explanation.failed_step().unwrap().check, "format");
+    }
+
+    #[test]
+    fn es_cif() {
+        use crate::validator::spain::{explain_cif, validate_cif};
+
+        assert!(validate_cif("A58818501"));
+        assert!(validate_cif("B-12345674"));
+        assert!(validate_cif("Q2826000H"));
+        assert!(validate_cif("G1234567D"));
+        assert!(validate_cif("G12345674"));
+        assert!(!validate_cif("Q28260008"));
+        assert!(!validate_cif("B1234567D"));
+        assert!(!validate_cif("A58818502"));
+        assert!(!validate_cif("I58818501"));
+        assert!(!validate_cif("84345642L"));
+        assert!(!validate_cif("A5881850"));
+
+        let explanation = explain_cif("Q28260008");
+        assert_eq!(
+            explanation.failed_step().unwrap().check,
+            "control character"
+        );
+    }
+
+    #[test]
+    fn es_document_type() {
+        use crate::validator::spain::{cif_entity, document_type, CifEntity, DocumentType};
+
+        assert_eq!(document_type("84345642L"), Some(DocumentType::Dni));
+        assert_eq!(document_type("Y3338121F"), Some(DocumentType::Nie));
+        assert_eq!(document_type("M0867756N"), Some(DocumentType::SpecialNif));
+        assert_eq!(document_type("A58818501"), Some(DocumentType::Cif));
+        assert_eq!(document_type("05756786M"), None);
+
+        assert_eq!(
+            cif_entity("A58818501"),
+            Some(CifEntity::PublicLimitedCompany)
+        );
+        assert_eq!(cif_entity("B12345674"), Some(CifEntity::LimitedCompany));
+        assert_eq!(cif_entity("Q2826000H"), Some(CifEntity::PublicBody));
+        assert_eq!(cif_entity("84345642L"), None);
     }
 }
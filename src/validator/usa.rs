@@ -1,8 +1,197 @@
 use crate::country::Code;
-use crate::{validator, Citizen};
+use crate::explanation::Explanation;
+use crate::validator::layout;
+use crate::validator::sanitizer;
+use crate::validator::sanitizer::SanitizeRules;
+use crate::validator::CountryValidator;
+use crate::{validator, Citizen, PlaceOfBirth};
 
 pub(crate) struct UsaValidator;
 
+const BLACKLIST: [&str; 3] = ["078051120", "219099999", "457555462"];
+
+/**
+ Area numbers given to each state before the randomization of 25 June 2011, from
+ https://www.ssa.gov/employer/stateweb.htm. The subdivision is empty for areas not tied to a state.
+**/
+const AREAS: [(u16, u16, &str, &str); 78] = [
+    (1, 3, "US-NH", "New Hampshire"),
+    (4, 7, "US-ME", "Maine"),
+    (8, 9, "US-VT", "Vermont"),
+    (10, 34, "US-MA", "Massachusetts"),
+    (35, 39, "US-RI", "Rhode Island"),
+    (40, 49, "US-CT", "Connecticut"),
+    (50, 134, "US-NY", "New York"),
+    (135, 158, "US-NJ", "New Jersey"),
+    (159, 211, "US-PA", "Pennsylvania"),
+    (212, 220, "US-MD", "Maryland"),
+    (221, 222, "US-DE", "Delaware"),
+    (223, 231, "US-VA", "Virginia"),
+    (232, 236, "US-WV", "West Virginia"),
+    (237, 246, "US-NC", "North Carolina"),
+    (247, 251, "US-SC", "South Carolina"),
+    (252, 260, "US-GA", "Georgia"),
+    (261, 267, "US-FL", "Florida"),
+    (268, 302, "US-OH", "Ohio"),
+    (303, 317, "US-IN", "Indiana"),
+    (318, 361, "US-IL", "Illinois"),
+    (362, 386, "US-MI", "Michigan"),
+    (387, 399, "US-WI", "Wisconsin"),
+    (400, 407, "US-KY", "Kentucky"),
+    (408, 415, "US-TN", "Tennessee"),
+    (416, 424, "US-AL", "Alabama"),
+    (425, 428, "US-MS", "Mississippi"),
+    (429, 432, "US-AR", "Arkansas"),
+    (433, 439, "US-LA", "Louisiana"),
+    (440, 448, "US-OK", "Oklahoma"),
+    (449, 467, "US-TX", "Texas"),
+    (468, 477, "US-MN", "Minnesota"),
+    (478, 485, "US-IA", "Iowa"),
+    (486, 500, "US-MO", "Missouri"),
+    (501, 502, "US-ND", "North Dakota"),
+    (503, 504, "US-SD", "South Dakota"),
+    (505, 508, "US-NE", "Nebraska"),
+    (509, 515, "US-KS", "Kansas"),
+    (516, 517, "US-MT", "Montana"),
+    (518, 519, "US-ID", "Idaho"),
+    (520, 520, "US-WY", "Wyoming"),
+    (521, 524, "US-CO", "Colorado"),
+    (525, 525, "US-NM", "New Mexico"),
+    (526, 527, "US-AZ", "Arizona"),
+    (528, 529, "US-UT", "Utah"),
+    (530, 530, "US-NV", "Nevada"),
+    (531, 539, "US-WA", "Washington"),
+    (540, 544, "US-OR", "Oregon"),
+    (545, 573, "US-CA", "California"),
+    (574, 574, "US-AK", "Alaska"),
+    (575, 576, "US-HI", "Hawaii"),
+    (577, 579, "US-DC", "District of Columbia"),
+    (580, 580, "", "Puerto Rico or Virgin Islands"),
+    (581, 584, "US-PR", "Puerto Rico"),
+    (585, 585, "US-NM", "New Mexico"),
+    (
+        586,
+        586,
+        "",
+        "Guam, American Samoa or Northern Mariana Islands",
+    ),
+    (587, 588, "US-MS", "Mississippi"),
+    (589, 595, "US-FL", "Florida"),
+    (596, 599, "US-PR", "Puerto Rico"),
+    (600, 601, "US-AZ", "Arizona"),
+    (602, 626, "US-CA", "California"),
+    (627, 645, "US-TX", "Texas"),
+    (646, 647, "US-UT", "Utah"),
+    (648, 649, "US-NM", "New Mexico"),
+    (650, 653, "US-CO", "Colorado"),
+    (654, 658, "US-SC", "South Carolina"),
+    (659, 665, "US-LA", "Louisiana"),
+    (667, 675, "US-GA", "Georgia"),
+    (676, 679, "US-AR", "Arkansas"),
+    (680, 680, "US-NV", "Nevada"),
+    (681, 690, "US-NC", "North Carolina"),
+    (691, 699, "US-VA", "Virginia"),
+    (700, 728, "", "Railroad Retirement Board"),
+    (729, 733, "", "Enumeration at Entry"),
+    (750, 751, "US-HI", "Hawaii"),
+    (752, 755, "US-MS", "Mississippi"),
+    (756, 763, "US-TN", "Tennessee"),
+    (764, 765, "US-AZ", "Arizona"),
+    (766, 772, "US-FL", "Florida"),
+];
+
+/**
+ Kind of a United States taxpayer identification number.
+
+ Individual Taxpayer Identification Numbers (ITIN) are given by the IRS to people who cannot get a
+ Social Security Number. They start with a 9 and their group number is 50-65, 70-88, 90-92 or 94-99.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    Ssn,
+    Itin,
+}
+
+/**
+ Returns whether a number is a valid SSN or ITIN, or `None` when it is neither.
+**/
+pub fn number_kind(id: &str) -> Option<NumberKind> {
+    if UsaValidator.validate_id(id) {
+        Some(NumberKind::Ssn)
+    } else if validate_itin(id) {
+        Some(NumberKind::Itin)
+    } else {
+        None
+    }
+}
+
+/**
+ Validates an Individual Taxpayer Identification Number.
+**/
+pub fn validate_itin(id: &str) -> bool {
+    let standard_id = UsaValidator.sanitize_id(id);
+    if standard_id.len() != 9 || !standard_id.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let group = standard_id[3..5].parse::<u8>().unwrap();
+    standard_id.starts_with('9') && matches!(group, 50..=65 | 70..=88 | 90..=92 | 94..=99)
+}
+
+/**
+ Returns the state where a valid SSN was issued, as an approximate place.
+
+ This only holds for numbers issued before the randomization of 25 June 2011: since then area
+ numbers are assigned at random and tell nothing about the state. Areas that were never assigned
+ before the randomization return `None`, see `issued_after_randomization`.
+**/
+pub fn issuing_state(id: &str) -> Option<PlaceOfBirth> {
+    if !UsaValidator.validate_id(id) {
+        return None;
+    }
+
+    let standard_id = UsaValidator.sanitize_id(id);
+    let area = standard_id[0..3].parse::<u16>().unwrap();
+    // Group 30 of area 232 was given to North Carolina.
+    if area == 232 && &standard_id[3..5] == "30" {
+        return Some(get_place(&standard_id[0..3], "US-NC", "North Carolina"));
+    }
+
+    AREAS
+        .iter()
+        .find(|(from, to, _, _)| (*from..=*to).contains(&area))
+        .map(|(_, _, subdivision, name)| get_place(&standard_id[0..3], subdivision, name))
+}
+
+/**
+ Tells whether a valid SSN has an area number that was never assigned before the randomization of
+ 25 June 2011, which means it was issued afterwards. `false` does not mean the number is older.
+**/
+pub fn issued_after_randomization(id: &str) -> Option<bool> {
+    if !UsaValidator.validate_id(id) {
+        return None;
+    }
+
+    let area = UsaValidator.sanitize_id(id)[0..3].parse::<u16>().unwrap();
+    Some(
+        !AREAS
+            .iter()
+            .any(|(from, to, _, _)| (*from..=*to).contains(&area)),
+    )
+}
+
+fn get_place(area: &str, subdivision: &str, name: &str) -> PlaceOfBirth {
+    let place = PlaceOfBirth::new(area, name)
+        .country("US")
+        .approximate()
+        .translation("en", name);
+    if subdivision.is_empty() {
+        place
+    } else {
+        place.subdivision(subdivision)
+    }
+}
+
 /**
  United States of America Social Security Number code validation.
 
@@ -14,40 +203,107 @@ pub(crate) struct UsaValidator;
  Another (english) version can be viewed in wikipedia: https://en.wikipedia.org/wiki/Social_Security_number#Valid_SSNs
 
  The list of regions can be checked in https://www.ssa.gov/employer/stateweb.htm
+
+ A SSN encodes neither the birth date nor the gender of its holder, so no citizen can be extracted.
+ The state where it was issued is available with `usa::issuing_state`.
 **/
 impl validator::CountryValidator for UsaValidator {
-    fn validate_id(&self, id: &str) -> bool {
+    fn check_id(&self, id: &str, trace: bool) -> Explanation {
         let standard_id = self.sanitize_id(id);
+        let mut explanation = Explanation::with_trace(id, &standard_id, trace);
 
-        if standard_id.len() != 9 {
-            return false;
-        }
-
-        if &standard_id == "078051120" || &standard_id == "219099999" || &standard_id == "457555462"
-        {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "length",
+                    standard_id.len() == 9 && standard_id.chars().all(|c| c.is_ascii_digit()),
+                )
+                .detail("expected", "9 digits")
+                .detail_with("actual", || standard_id.chars().count()),
+        ) {
+            return explanation;
         }
 
-        let area_code = &standard_id[0..3].parse::<u16>().unwrap();
-        if area_code == &0 || area_code == &666 || area_code > &899 {
-            return false;
+        if !explanation.check(
+            explanation
+                .step("blacklist", !BLACKLIST.contains(&standard_id.as_str()))
+                .detail_with("blacklisted numbers", || BLACKLIST.join(", ")),
+        ) {
+            return explanation;
         }
 
-        if &standard_id[3..5].parse::<u8>().unwrap() == &0 {
-            return false;
+        let area_code = standard_id[0..3].parse::<u16>().unwrap();
+        if !explanation.check(
+            explanation
+                .step(
+                    "area number",
+                    area_code != 0 && area_code != 666 && area_code <= 899,
+                )
+                .detail("expected", "001-899, except 666")
+                .detail("actual", &standard_id[0..3]),
+        ) {
+            return explanation;
         }
 
-        if &standard_id[5..].parse::<u16>().unwrap() == &0 {
-            return false;
+        if !explanation.check(
+            explanation
+                .step(
+                    "group number",
+                    standard_id[3..5].parse::<u8>().unwrap() != 0,
+                )
+                .detail("expected", "01-99")
+                .detail("actual", &standard_id[3..5]),
+        ) {
+            return explanation;
         }
 
-        return true;
+        explanation.check(
+            explanation
+                .step(
+                    "serial number",
+                    standard_id[5..].parse::<u16>().unwrap() != 0,
+                )
+                .detail("expected", "0001-9999")
+                .detail("actual", &standard_id[5..]),
+        );
+        explanation
     }
 
     fn country_code(&self) -> Code {
         return crate::country::Code::US;
     }
 
+    fn layout(&self) -> &'static str {
+        "DDDDDDDDD"
+    }
+
+    fn validate_prefix(&self, prefix: &str) -> Result<(), String> {
+        layout::check_layout(prefix, self.layout())?;
+        layout::check_number(prefix, 0, 3, 1..=899, "area number")?;
+        if prefix.len() >= 3 && &prefix[0..3] == "666" {
+            return Err("invalid area number '666'".to_string());
+        }
+        layout::check_number(prefix, 3, 5, 1..=99, "group number")?;
+        layout::check_number(prefix, 5, 9, 1..=9999, "serial number")
+    }
+
+    fn sanitize_rules(&self) -> SanitizeRules {
+        SanitizeRules {
+            separator_positions: &[3, 5],
+            ..sanitizer::DEFAULT_RULES
+        }
+    }
+
+    fn format_id(&self, id: &str) -> String {
+        let standard_id = self.sanitize_id(id);
+        format!(
+            "{}-{}-{}",
+            &standard_id[0..3],
+            &standard_id[3..5],
+            &standard_id[5..9]
+        )
+    }
+
     fn extract_citizen(&self, _id: &str) -> Option<Citizen> {
         return None;
     }
@@ -82,4 +338,56 @@ mod tests {
         assert_eq!(validator.validate_id("574-22-7664"), true);
         assert_eq!(validator.validate_id("671-26-9121"), true);
     }
+
+    #[test]
+    fn us_explain() {
+        let validator = super::validator::usa::UsaValidator;
+        let explanation = validator.explain("167-38-1265");
+        assert!(explanation.is_valid());
+        assert_eq!(explanation.steps.last().unwrap().check, "serial number");
+
+        let explanation = validator.explain("666-91-8271");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.failed_step().unwrap().check, "area number");
+    }
+
+    #[test]
+    fn us_issuing_state() {
+        use crate::validator::usa::{issued_after_randomization, issuing_state};
+
+        let place = issuing_state("536-22-8726").unwrap();
+        assert_eq!(place.code, "536");
+        assert_eq!(place.name, "Washington");
+        assert_eq!(place.subdivision.as_deref(), Some("US-WA"));
+        assert_eq!(place.country.as_deref(), Some("US"));
+        assert!(place.approximate);
+
+        assert_eq!(issuing_state("167-38-1265").unwrap().name, "Pennsylvania");
+        assert_eq!(issuing_state("232-30-1234").unwrap().name, "North Carolina");
+        assert_eq!(issuing_state("232-31-1234").unwrap().name, "West Virginia");
+        assert!(issuing_state("586-12-1234").unwrap().subdivision.is_none());
+        assert!(issuing_state("773-12-1234").is_none());
+        assert!(issuing_state("666-91-8271").is_none());
+
+        assert_eq!(issued_after_randomization("536-22-8726"), Some(false));
+        assert_eq!(issued_after_randomization("773-12-1234"), Some(true));
+        assert_eq!(issued_after_randomization("740-12-1234"), Some(true));
+        assert_eq!(issued_after_randomization("666-91-8271"), None);
+    }
+
+    #[test]
+    fn us_itin() {
+        use crate::validator::usa::{number_kind, validate_itin, NumberKind};
+
+        assert!(validate_itin("912-70-1234"));
+        assert!(validate_itin("999-99-9999"));
+        assert!(!validate_itin("912-69-1234"));
+        assert!(!validate_itin("912-93-1234"));
+        assert!(!validate_itin("812-70-1234"));
+        assert!(!validate_itin("912-70-123"));
+
+        assert_eq!(number_kind("536-22-8726"), Some(NumberKind::Ssn));
+        assert_eq!(number_kind("912-70-1234"), Some(NumberKind::Itin));
+        assert_eq!(number_kind("912-12-1234"), None);
+    }
 }
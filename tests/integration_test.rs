@@ -41,7 +41,7 @@ fn extract_france_test() {
     assert_eq!(citizen.gender, 'F');
     assert_eq!(citizen.year_of_birth, 1982);
     assert_eq!(citizen.month_of_birth.unwrap(), 8);
-    assert_eq!(citizen.place_of_birth.unwrap(), "Corrèze");
+    assert_eq!(citizen.place_of_birth.unwrap().name, "Corrèze");
 }
 
 #[test]
@@ -59,3 +59,274 @@ fn generator_denmark() {
     .unwrap();
     assert_eq!(id, "160691-3113");
 }
+
+#[test]
+fn national_id_brazil() {
+    let id =
+        socrates_rs::NationalId::parse("144-416-762.63", socrates_rs::country::Code::BR).unwrap();
+    assert_eq!(
+        id,
+        socrates_rs::NationalId::parse("14441676263", socrates_rs::country::Code::BR).unwrap()
+    );
+    assert_eq!(id.format(), "144.416.762-63");
+    assert!(socrates_rs::NationalId::parse("23294954040", socrates_rs::country::Code::BR).is_err());
+}
+
+#[test]
+fn validate_id_strict_denmark() {
+    assert!(socrates_rs::validate_id_strict(
+        "090792-1395",
+        socrates_rs::country::Code::DK
+    ));
+    assert!(!socrates_rs::validate_id_strict(
+        "0907-921395",
+        socrates_rs::country::Code::DK
+    ));
+    assert!(socrates_rs::validate_id(
+        "0907-921395",
+        socrates_rs::country::Code::DK
+    ));
+}
+
+#[test]
+fn explain_denmark() {
+    let explanation = socrates_rs::explain("230321-2454", socrates_rs::country::Code::DK);
+    assert!(!explanation.is_valid());
+    assert_eq!(explanation.sanitized, "2303212454");
+    let failed = explanation.failed_step().unwrap();
+    assert_eq!(failed.check, "modulus 11");
+    assert!(explanation.to_string().contains("[fail] modulus 11"));
+}
+
+#[test]
+fn suggest_corrections_brazil() {
+    let suggestions =
+        socrates_rs::suggest_corrections("144.416.762-36", socrates_rs::country::Code::BR);
+    assert!(suggestions.contains(&"14441676263".to_string()));
+    for suggestion in suggestions {
+        assert!(socrates_rs::validate_id(
+            &suggestion,
+            socrates_rs::country::Code::BR
+        ));
+    }
+}
+
+#[test]
+fn validate_partial_mexico() {
+    use socrates_rs::PartialStatus;
+    assert_eq!(
+        socrates_rs::validate_partial("JOIM8901", socrates_rs::country::Code::MX),
+        PartialStatus::Incomplete
+    );
+    assert!(matches!(
+        socrates_rs::validate_partial("JOIM8913", socrates_rs::country::Code::MX),
+        PartialStatus::InvalidPrefix(_)
+    ));
+    assert_eq!(
+        socrates_rs::validate_partial("JOIM890106HHGSMN08", socrates_rs::country::Code::MX),
+        PartialStatus::Complete(true)
+    );
+}
+
+#[test]
+fn verify_against_italy() {
+    use socrates_rs::FieldCheck;
+    let claim = Citizen {
+        gender: 'M',
+        year_of_birth: 2025,
+        month_of_birth: Some(4),
+        day_of_birth: Some(9),
+        place_of_birth: Some(socrates_rs::PlaceOfBirth::named("Milano")),
+    };
+    let name = socrates_rs::Name {
+        first_name: "Matteo".to_string(),
+        last_name: "Moretti".to_string(),
+        second_last_name: None,
+    };
+    let report = socrates_rs::verify_against_with_name(
+        "MRTMTT25D09F205Z",
+        socrates_rs::country::Code::IT,
+        &claim,
+        &name,
+    );
+    assert!(report.is_consistent());
+    assert_eq!(report.place_of_birth, FieldCheck::Match);
+    assert_eq!(report.name, FieldCheck::Match);
+
+    let report =
+        socrates_rs::verify_against("MRTMTT25D09F205Z", socrates_rs::country::Code::IT, &claim);
+    assert_eq!(report.name, FieldCheck::NotClaimed);
+}
+
+#[test]
+fn italy_omocodic_base_code() {
+    assert!(socrates_rs::validate_id(
+        "MRTMTTNRDLVFNLRV",
+        socrates_rs::country::Code::IT
+    ));
+    assert_eq!(
+        socrates_rs::italy::base_code("MRTMTTNRDLVFNLRV").unwrap(),
+        "MRTMTT25D09F205Z"
+    );
+}
+
+#[test]
+fn italy_name_codes() {
+    assert_eq!(socrates_rs::italy::surname_code("Moretti"), "MRT");
+    assert_eq!(socrates_rs::italy::name_code("Matteo"), "MTT");
+    assert!(socrates_rs::italy::matches_name(
+        "MRTMTT25D09F205Z",
+        "Matteo",
+        "Moretti"
+    ));
+}
+
+#[test]
+fn italy_validate_birthplace() {
+    assert!(socrates_rs::italy::validate_birthplace("RSSMRA80A01Z135P"));
+    assert!(!socrates_rs::italy::validate_birthplace("RSSMRA95A01Z135W"));
+}
+
+#[test]
+fn italy_foreign_birthplace() {
+    match socrates_rs::italy::birthplace("MLLSNT82P65Z404U").unwrap() {
+        socrates_rs::italy::Birthplace::Foreign { country, .. } => {
+            assert!(socrates_rs::validate_id("536-22-8726", country.unwrap()));
+        }
+        _ => panic!("expected a foreign birthplace"),
+    }
+}
+
+#[test]
+fn france_corsica_and_foreign_births() {
+    assert!(socrates_rs::validate_id(
+        "1 85 05 2A 004 123 56",
+        socrates_rs::country::Code::FR
+    ));
+    let citizen =
+        socrates_rs::extract_information("1900399109012 12", socrates_rs::country::Code::FR)
+            .unwrap();
+    let place_of_birth = citizen.place_of_birth.unwrap();
+    assert!(place_of_birth.foreign_born);
+    assert_eq!(place_of_birth.country.as_deref(), Some("DE"));
+}
+
+#[test]
+fn denmark_without_modulus_11() {
+    assert!(socrates_rs::validate_id(
+        "011007-4001",
+        socrates_rs::country::Code::DK
+    ));
+    assert!(socrates_rs::NationalId::parse("011007-4001", socrates_rs::country::Code::DK).is_ok());
+    let cpr = socrates_rs::denmark::extract_cpr("011007-4001").unwrap();
+    assert_eq!(cpr.citizen.year_of_birth, 2007);
+    assert!(!cpr.passes_modulus_11);
+    let report =
+        socrates_rs::verify_against("011007-4001", socrates_rs::country::Code::DK, &cpr.citizen);
+    assert!(report.valid_id);
+    assert!(report.is_consistent());
+    assert!(socrates_rs::denmark::validate_id_without_modulus_11(
+        "011007-4001"
+    ));
+    assert!(!socrates_rs::denmark::validate_id_without_modulus_11(
+        "230321-2454"
+    ));
+    assert_eq!(
+        socrates_rs::denmark::passes_modulus_11("011007-4001"),
+        Some(false)
+    );
+}
+
+#[test]
+fn usa_issuing_state_and_itin() {
+    let place = socrates_rs::usa::issuing_state("536-22-8726").unwrap();
+    assert_eq!(place.subdivision.as_deref(), Some("US-WA"));
+    assert!(place.approximate);
+    assert!(!socrates_rs::validate_id(
+        "912-70-1234",
+        socrates_rs::country::Code::US
+    ));
+    assert_eq!(
+        socrates_rs::usa::number_kind("912-70-1234"),
+        Some(socrates_rs::usa::NumberKind::Itin)
+    );
+}
+
+#[test]
+fn canada_registration_province() {
+    let place = socrates_rs::canada::registration_province("501 343 719").unwrap();
+    assert_eq!(place.subdivision.as_deref(), Some("CA-ON"));
+    assert_eq!(
+        socrates_rs::canada::is_temporary_resident("912 046 737"),
+        Some(true)
+    );
+}
+
+#[test]
+fn brazil_fiscal_region_and_cnpj() {
+    let place = socrates_rs::brazil::fiscal_region("26363102820").unwrap();
+    assert_eq!(place.subdivision.as_deref(), Some("BR-SP"));
+    assert!(socrates_rs::brazil::validate_cnpj("12.ABC.345/01DE-35"));
+    assert_eq!(
+        socrates_rs::brazil::format_cnpj("11222333000181").as_deref(),
+        Some("11.222.333/0001-81")
+    );
+    assert!(socrates_rs::brazil::format_cnpj("123").is_none());
+}
+
+#[test]
+fn spain_document_types() {
+    assert!(socrates_rs::validate_id(
+        "K1234567L",
+        socrates_rs::country::Code::ES
+    ));
+    assert_eq!(
+        socrates_rs::spain::document_type("Q2826000H"),
+        Some(socrates_rs::spain::DocumentType::Cif)
+    );
+    assert_eq!(
+        socrates_rs::spain::cif_entity("Q2826000H"),
+        Some(socrates_rs::spain::CifEntity::PublicBody)
+    );
+}
+
+#[test]
+fn portugal_nif_and_niss() {
+    assert_eq!(
+        socrates_rs::portugal::nif_entity("500697256"),
+        Some(socrates_rs::portugal::NifEntity::Company)
+    );
+    assert!(socrates_rs::portugal::validate_niss("11234567892"));
+}
+
+#[test]
+fn portugal_citizen_card_reissues() {
+    assert_eq!(
+        socrates_rs::portugal::reissue_count("11084129 8 ZX8"),
+        Some(2)
+    );
+}
+
+#[test]
+fn germany_documents() {
+    assert!(socrates_rs::germany::validate_id_card_number("T220001293"));
+    let document = socrates_rs::germany::parse_mrz(
+        "IDD<<T220001293<<<<<<<<<<<<<<<\n6408125<2010315D<<<<<<<<<<<<<4\nMUSTERMANN<<ERIKA<<<<<<<<<<<<<",
+    )
+    .unwrap();
+    assert_eq!(document.document_number, "T22000129");
+    assert_eq!(document.date_of_birth.unwrap().to_string(), "1964-08-12");
+}
+
+#[test]
+fn luxembourg_date_of_birth() {
+    let citizen =
+        socrates_rs::extract_information("1983 0812 467 83", socrates_rs::country::Code::LU)
+            .unwrap();
+    assert_eq!(citizen.year_of_birth, 1983);
+    assert_eq!(citizen.gender, 'U');
+    assert!(!socrates_rs::validate_id(
+        "1994789587182",
+        socrates_rs::country::Code::LU
+    ));
+}